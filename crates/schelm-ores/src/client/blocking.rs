@@ -0,0 +1,237 @@
+//! Blocking API client.
+//!
+//! Wraps the async [`Client`](crate::client::Client) and drives it on an
+//! internal single-threaded tokio runtime, for use in synchronous programs such
+//! as CLI tools and build scripts.
+//!
+//! The blocking client must not be used from within an async runtime; calls
+//! would panic when trying to block the runtime's thread.
+//!
+//! This module is behind the Cargo feature `blocking`.
+
+use crate::client::endpoints::responses as async_responses;
+use crate::client::{ClientBuilder, Error, ResponseEventStream, Result};
+use crate::models;
+
+use futures_core::Stream;
+
+use std::pin::Pin;
+use std::sync::Arc;
+
+/// Blocking API client, created via [`ClientBuilder::build_blocking`] or
+/// [`Client::new`].
+#[derive(Clone, Debug)]
+pub struct Client {
+    inner: crate::client::Client,
+    runtime: Arc<tokio::runtime::Runtime>,
+}
+
+impl Client {
+    /// Creates a [`ClientBuilder`] with required fields.
+    ///
+    /// Finish with [`ClientBuilder::build_blocking`].
+    pub fn builder(api_key: impl Into<String>, base_url: url::Url) -> ClientBuilder {
+        ClientBuilder::new(api_key, base_url)
+    }
+
+    /// Wraps an async client.
+    ///
+    /// Fails with [`Error::Config`] if the internal runtime can't be started.
+    pub fn new(client: crate::client::Client) -> Result<Self> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| Error::Config(format!("failed to start the tokio runtime: {e}")))?;
+        Ok(Self {
+            inner: client,
+            runtime: Arc::new(runtime),
+        })
+    }
+
+    /// Access the Responses endpoint group.
+    pub fn responses(&self) -> Responses<'_> {
+        Responses { client: self }
+    }
+
+    /// Returns the wrapped async client.
+    pub fn as_async(&self) -> &crate::client::Client {
+        &self.inner
+    }
+}
+
+/// Blocking Responses endpoint group.
+#[derive(Clone, Copy, Debug)]
+pub struct Responses<'a> {
+    client: &'a Client,
+}
+
+impl<'a> Responses<'a> {
+    /// Creates a new response.
+    ///
+    /// `model` and `input` are required and are provided at construction time.
+    pub fn create(
+        &self,
+        model: impl Into<String>,
+        input: models::CreateResponseInput,
+    ) -> CreateResponseRequestBuilder<'a> {
+        CreateResponseRequestBuilder {
+            inner: self.client.inner.responses().create(model, input),
+            runtime: &self.client.runtime,
+        }
+    }
+
+    /// Convenience helper to create a response from a single user text input.
+    pub fn create_text(
+        &self,
+        model: impl Into<String>,
+        text: impl Into<String>,
+    ) -> CreateResponseRequestBuilder<'a> {
+        self.create(model, models::CreateResponseInput::String(text.into()))
+    }
+}
+
+/// Blocking request builder for `POST /responses`.
+///
+/// Offers the same setters as the async
+/// [`CreateResponseRequestBuilder`](async_responses::CreateResponseRequestBuilder).
+#[derive(Debug)]
+pub struct CreateResponseRequestBuilder<'a> {
+    inner: async_responses::CreateResponseRequestBuilder<'a>,
+    runtime: &'a Arc<tokio::runtime::Runtime>,
+}
+
+/// Forwards by-value setters to the wrapped async builder.
+macro_rules! forward_setters {
+    ($($(#[$meta:meta])* fn $name:ident($($arg:ident: $ty:ty),*);)*) => {
+        $(
+            $(#[$meta])*
+            pub fn $name(mut self, $($arg: $ty),*) -> Self {
+                self.inner = self.inner.$name($($arg),*);
+                self
+            }
+        )*
+    };
+}
+
+impl<'a> CreateResponseRequestBuilder<'a> {
+    forward_setters! {
+        /// Sets the system instructions.
+        fn instructions(instructions: impl Into<String>);
+        /// Sets the sampling temperature, between `0` and `2`.
+        fn temperature(temperature: f64);
+        /// Sets an upper bound for the number of output tokens.
+        fn max_output_tokens(max_output_tokens: i32);
+        /// Sets the tools the model may call.
+        fn tools(tools: Vec<models::ResponsesToolParam>);
+        /// Sets how the model selects tools.
+        fn tool_choice(tool_choice: models::ToolChoiceParam);
+        /// Sets the text output configuration.
+        fn text(text: models::TextParam);
+        /// Sets the service tier.
+        fn service_tier(service_tier: models::ServiceTierEnum);
+        /// Sets nucleus sampling, between `0` and `1`.
+        fn top_p(top_p: f64);
+        /// Sets the truncation strategy.
+        fn truncation(truncation: models::TruncationEnum);
+        /// Sets the reasoning configuration.
+        fn reasoning(reasoning: models::ReasoningParam);
+        /// Continues from a previous response.
+        fn previous_response_id(id: impl Into<String>);
+        /// Attaches the request to a conversation.
+        fn conversation(id: impl Into<String>);
+        /// Runs the request in the background. Also sets `store=true`.
+        fn background(background: bool);
+        /// Adds additional output data to include in the response.
+        fn include(include: Vec<models::IncludeEnum>);
+        /// Attaches up to 16 key-value pairs to the response.
+        fn metadata(metadata: models::MetadataParam);
+        /// Sets the presence penalty, between `-2` and `2`.
+        fn presence_penalty(presence_penalty: f64);
+        /// Sets the frequency penalty, between `-2` and `2`.
+        fn frequency_penalty(frequency_penalty: f64);
+        /// Whether the model may call tools in parallel.
+        fn parallel_tool_calls(parallel_tool_calls: bool);
+        /// Sets options for streamed responses. Only applies to [`send_stream`](Self::send_stream).
+        fn stream_options(stream_options: models::StreamOptionsParam);
+        /// Sets an upper bound for the number of tool calls.
+        fn max_tool_calls(max_tool_calls: i32);
+        /// Sets a stable identifier for the end user.
+        fn safety_identifier(safety_identifier: impl Into<String>);
+        /// Sets a key for prompt caching.
+        fn prompt_cache_key(prompt_cache_key: impl Into<String>);
+        /// Whether to store the response so it can be retrieved later. Defaults to `false`.
+        fn store(store: bool);
+        /// Sets the number of most likely tokens to return per position, between `0` and `20`.
+        fn top_logprobs(top_logprobs: i32);
+        /// Adds a field the typed setters don't cover to the request body.
+        fn extra_body(key: impl Into<String>, value: impl Into<serde_json::Value>);
+        /// Adds a header to this request, overriding client-wide defaults.
+        fn extra_header(name: impl Into<String>, value: impl Into<String>);
+        /// Adds a query parameter to the request URL.
+        fn extra_query(key: impl Into<String>, value: impl Into<String>);
+        /// Overrides the client-wide timeout for this request.
+        fn timeout(timeout: std::time::Duration);
+        /// Aborts the request, or the returned iterator, once `token` is cancelled.
+        fn cancellation_token(token: crate::client::CancellationToken);
+        /// Sets the `Idempotency-Key` instead of generating one.
+        fn idempotency_key(key: impl Into<String>);
+        /// Adds a model to replay the request with on capacity or context-length errors.
+        fn fallback(fallback: impl Into<async_responses::ModelFallback>);
+    }
+
+    /// Checks the request against the documented parameter constraints.
+    pub fn validate(&self) -> Result<()> {
+        self.inner.validate()
+    }
+
+    /// Sends the request and returns the full response resource.
+    pub fn send(self) -> Result<models::ResponseResource> {
+        self.runtime.block_on(self.inner.send())
+    }
+
+    /// Sends the request with streaming enabled and returns an iterator over
+    /// the events.
+    pub fn send_stream(self) -> Result<ResponseEventIter> {
+        let stream = self.runtime.block_on(self.inner.send_stream())?;
+        Ok(ResponseEventIter {
+            stream,
+            runtime: Arc::clone(self.runtime),
+        })
+    }
+}
+
+/// A blocking iterator over the events of a streamed response.
+///
+/// Each call to [`next`](Iterator::next) blocks until the next event arrives.
+/// The iterator ends after the last event or the first error.
+pub struct ResponseEventIter {
+    stream: ResponseEventStream,
+    runtime: Arc<tokio::runtime::Runtime>,
+}
+
+impl ResponseEventIter {
+    /// Ends the iterator with an error when no data arrives for `timeout`.
+    ///
+    /// See [`ResponseEventStream::idle_timeout`].
+    pub fn idle_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.stream = self.stream.idle_timeout(timeout);
+        self
+    }
+}
+
+impl std::fmt::Debug for ResponseEventIter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ResponseEventIter").finish_non_exhaustive()
+    }
+}
+
+impl Iterator for ResponseEventIter {
+    type Item = Result<models::StreamingEvent>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let stream = &mut self.stream;
+        self.runtime.block_on(std::future::poll_fn(|cx| {
+            Pin::new(&mut *stream).poll_next(cx)
+        }))
+    }
+}
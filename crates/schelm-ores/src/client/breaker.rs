@@ -0,0 +1,584 @@
+//! Failing fast while an endpoint or model keeps failing.
+
+use crate::client::{Error, Result, http};
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// The state of a single circuit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CircuitState {
+    /// Requests are sent normally.
+    Closed,
+    /// Requests fail with [`Error::CircuitOpen`] without being sent.
+    Open,
+    /// A single probe request is sent; its outcome closes or reopens the circuit.
+    HalfOpen,
+}
+
+/// A transition of a circuit, as passed to
+/// [`CircuitBreaker::on_state_change`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CircuitStateChange {
+    /// The base URL of the client the circuit belongs to.
+    pub base_url: String,
+    /// The model of the requests the circuit belongs to, if they name one.
+    pub model: Option<String>,
+    /// The previous state.
+    pub from: CircuitState,
+    /// The new state.
+    pub to: CircuitState,
+}
+
+/// Stops sending requests to a base URL and model after repeated failures.
+///
+/// Every combination of base URL and model has its own circuit. A circuit
+/// opens after [`failure_threshold`](Self::failure_threshold) consecutive
+/// failures, or when the share of failures among the last
+/// [`window_size`](Self::window_size) requests reaches the
+/// [`error_rate_threshold`](Self::error_rate_threshold). Connection errors,
+/// timeouts and `5xx` responses count as failures; each retry attempt counts
+/// separately.
+///
+/// While a circuit is open, requests fail with [`Error::CircuitOpen`]. After
+/// [`open_duration`](Self::open_duration), the circuit becomes half-open and
+/// lets a single request through: if it succeeds the circuit closes,
+/// otherwise it opens again.
+///
+/// At most [`max_circuits`](Self::max_circuits) circuits are kept. When a
+/// request needs a new circuit beyond that, circuits that are closed or whose
+/// open duration has passed are forgotten, starting with the least recently
+/// used ones.
+///
+/// Register a breaker with
+/// [`ClientBuilder::circuit_breaker`](crate::client::ClientBuilder::circuit_breaker).
+/// Clones share their circuits, so a clone can be kept to query
+/// [`state`](Self::state).
+#[derive(Clone)]
+pub struct CircuitBreaker {
+    config: Arc<Config>,
+    circuits: Arc<Mutex<HashMap<CircuitKey, Circuit>>>,
+}
+
+/// Called with every state change of a circuit.
+type StateChangeCallback = dyn Fn(&CircuitStateChange) + Send + Sync;
+
+#[derive(Clone)]
+struct Config {
+    failure_threshold: u32,
+    error_rate_threshold: f64,
+    window_size: usize,
+    open_duration: Duration,
+    max_circuits: usize,
+    on_state_change: Option<Arc<StateChangeCallback>>,
+}
+
+/// Identifies a circuit.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub(crate) struct CircuitKey {
+    base_url: String,
+    model: Option<String>,
+}
+
+impl CircuitKey {
+    /// Returns the key for `request`, taking the model from its JSON body.
+    pub(crate) fn new(base_url: &url::Url, request: &http::Request) -> Self {
+        #[derive(serde::Deserialize)]
+        struct Model {
+            model: Option<String>,
+        }
+
+        let model = request
+            .body
+            .as_ref()
+            .and_then(|body| serde_json::from_slice::<Model>(body).ok())
+            .and_then(|body| body.model);
+        Self {
+            base_url: base_url.to_string(),
+            model,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Circuit {
+    state: CircuitState,
+    consecutive_failures: u32,
+    /// The outcomes of the latest requests while closed, `true` for failures.
+    window: VecDeque<bool>,
+    opened_at: Instant,
+    last_used: Instant,
+    probing: bool,
+    /// Bumped on every transition, so outcomes of requests let through in an
+    /// earlier state are ignored.
+    generation: u64,
+}
+
+impl Default for CircuitBreaker {
+    /// Opens after 5 consecutive failures or a 50% failure rate among the
+    /// last 20 requests, and probes again after 30s. Keeps up to 1024
+    /// circuits.
+    fn default() -> Self {
+        Self {
+            config: Arc::new(Config {
+                failure_threshold: 5,
+                error_rate_threshold: 0.5,
+                window_size: 20,
+                open_duration: Duration::from_secs(30),
+                max_circuits: 1024,
+                on_state_change: None,
+            }),
+            circuits: Arc::default(),
+        }
+    }
+}
+
+impl std::fmt::Debug for CircuitBreaker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CircuitBreaker")
+            .field("failure_threshold", &self.config.failure_threshold)
+            .field("error_rate_threshold", &self.config.error_rate_threshold)
+            .field("window_size", &self.config.window_size)
+            .field("open_duration", &self.config.open_duration)
+            .field("max_circuits", &self.config.max_circuits)
+            .finish_non_exhaustive()
+    }
+}
+
+impl CircuitBreaker {
+    /// Creates a breaker with the [default](Self::default) thresholds.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the number of consecutive failures that opens a circuit.
+    pub fn failure_threshold(mut self, failures: u32) -> Self {
+        self.config_mut().failure_threshold = failures.max(1);
+        self
+    }
+
+    /// Sets the share of failures, between `0.0` and `1.0`, that opens a
+    /// circuit. A threshold above `1.0` disables this check.
+    pub fn error_rate_threshold(mut self, rate: f64) -> Self {
+        self.config_mut().error_rate_threshold = rate.max(0.0);
+        self
+    }
+
+    /// Sets the number of latest requests the error rate is computed over.
+    ///
+    /// The error rate is only checked once that many requests completed.
+    pub fn window_size(mut self, requests: usize) -> Self {
+        self.config_mut().window_size = requests.max(1);
+        self
+    }
+
+    /// Sets how long a circuit stays open before it lets a probe request through.
+    pub fn open_duration(mut self, duration: Duration) -> Self {
+        self.config_mut().open_duration = duration;
+        self
+    }
+
+    /// Sets the number of circuits that are kept before idle ones are
+    /// forgotten.
+    pub fn max_circuits(mut self, circuits: usize) -> Self {
+        self.config_mut().max_circuits = circuits.max(1);
+        self
+    }
+
+    /// Calls `callback` whenever a circuit changes its state.
+    ///
+    /// The callback runs on the task that sent the request, so it should
+    /// return quickly.
+    pub fn on_state_change(
+        mut self,
+        callback: impl Fn(&CircuitStateChange) + Send + Sync + 'static,
+    ) -> Self {
+        self.config_mut().on_state_change = Some(Arc::new(callback));
+        self
+    }
+
+    /// Returns the state of the circuit for `base_url` and `model`.
+    ///
+    /// Circuits that haven't seen a request yet, or were forgotten, are
+    /// closed. An open circuit
+    /// is reported as open until the next request turns it half-open.
+    pub fn state(&self, base_url: &url::Url, model: Option<&str>) -> CircuitState {
+        let key = CircuitKey {
+            base_url: http::normalize_base_url(base_url.clone()).to_string(),
+            model: model.map(str::to_owned),
+        };
+        self.lock()
+            .get(&key)
+            .map_or(CircuitState::Closed, |circuit| circuit.state)
+    }
+
+    /// Lets a request for `key` through, or fails with [`Error::CircuitOpen`].
+    pub(crate) fn acquire(&self, key: CircuitKey) -> Result<Permit<'_>> {
+        self.acquire_at(key, Instant::now())
+    }
+
+    fn acquire_at(&self, key: CircuitKey, now: Instant) -> Result<Permit<'_>> {
+        let mut circuits = self.lock();
+        if !circuits.contains_key(&key) && circuits.len() >= self.config.max_circuits {
+            self.evict(&mut circuits, now);
+        }
+        let circuit = circuits.entry(key.clone()).or_insert_with(|| Circuit {
+            state: CircuitState::Closed,
+            consecutive_failures: 0,
+            window: VecDeque::new(),
+            opened_at: now,
+            last_used: now,
+            probing: false,
+            generation: 0,
+        });
+        circuit.last_used = now;
+
+        let mut change = None;
+        match circuit.state {
+            CircuitState::Closed => {}
+            CircuitState::Open if now >= circuit.opened_at + self.config.open_duration => {
+                change = Some(circuit.transition(CircuitState::HalfOpen));
+                circuit.probing = true;
+            }
+            CircuitState::HalfOpen if !circuit.probing => circuit.probing = true,
+            CircuitState::Open | CircuitState::HalfOpen => {
+                return Err(Error::CircuitOpen {
+                    base_url: key.base_url,
+                    model: key.model,
+                });
+            }
+        }
+        let probe = circuit.state == CircuitState::HalfOpen;
+        let generation = circuit.generation;
+        drop(circuits);
+
+        if let Some((from, to)) = change {
+            self.notify(&key, from, to);
+        }
+        Ok(Permit {
+            breaker: self,
+            key,
+            probe,
+            generation,
+            recorded: false,
+        })
+    }
+
+    /// Records the outcome of a request let through by [`acquire`](Self::acquire).
+    fn record(&self, permit: &Permit<'_>, failed: bool, now: Instant) {
+        let config = &self.config;
+        let mut circuits = self.lock();
+        let Some(circuit) = circuits.get_mut(&permit.key) else {
+            return;
+        };
+        // The circuit changed its state since the request was let through,
+        // e.g. a slow request admitted while closed that finishes while the
+        // circuit is half-open must not be taken as the probe's outcome.
+        if circuit.generation != permit.generation {
+            return;
+        }
+
+        let change = match circuit.state {
+            CircuitState::HalfOpen if !permit.probe => None,
+            CircuitState::HalfOpen if failed => {
+                circuit.opened_at = now;
+                Some(circuit.transition(CircuitState::Open))
+            }
+            CircuitState::HalfOpen => Some(circuit.transition(CircuitState::Closed)),
+            CircuitState::Closed => {
+                circuit.consecutive_failures = if failed {
+                    circuit.consecutive_failures + 1
+                } else {
+                    0
+                };
+                circuit.window.push_back(failed);
+                if circuit.window.len() > config.window_size {
+                    circuit.window.pop_front();
+                }
+                let failures = circuit.window.iter().filter(|&&failed| failed).count();
+                let error_rate = failures as f64 / circuit.window.len() as f64;
+                let rate_exceeded = circuit.window.len() == config.window_size
+                    && error_rate >= config.error_rate_threshold;
+                if failed
+                    && (circuit.consecutive_failures >= config.failure_threshold || rate_exceeded)
+                {
+                    circuit.opened_at = now;
+                    Some(circuit.transition(CircuitState::Open))
+                } else {
+                    None
+                }
+            }
+            // No requests are let through while open.
+            CircuitState::Open => None,
+        };
+        drop(circuits);
+
+        if let Some((from, to)) = change {
+            self.notify(&permit.key, from, to);
+        }
+    }
+
+    /// Makes room for a new circuit by forgetting circuits that don't hold
+    /// back any requests.
+    ///
+    /// Circuits unused for longer than the open duration are dropped first;
+    /// if that isn't enough, the least recently used one is.
+    fn evict(&self, circuits: &mut HashMap<CircuitKey, Circuit>, now: Instant) {
+        let open_duration = self.config.open_duration;
+        let evictable = |circuit: &Circuit| match circuit.state {
+            CircuitState::Closed => true,
+            CircuitState::Open => now >= circuit.opened_at + open_duration,
+            CircuitState::HalfOpen => false,
+        };
+
+        circuits
+            .retain(|_, circuit| !evictable(circuit) || now < circuit.last_used + open_duration);
+        if circuits.len() < self.config.max_circuits {
+            return;
+        }
+        let least_recently_used = circuits
+            .iter()
+            .filter(|(_, circuit)| evictable(circuit))
+            .min_by_key(|(_, circuit)| circuit.last_used)
+            .map(|(key, _)| key.clone());
+        if let Some(key) = least_recently_used {
+            circuits.remove(&key);
+        }
+    }
+
+    /// Gives up the probe of a half-open circuit without an outcome.
+    fn release(&self, permit: &Permit<'_>) {
+        if let Some(circuit) = self.lock().get_mut(&permit.key)
+            && circuit.generation == permit.generation
+        {
+            circuit.probing = false;
+        }
+    }
+
+    fn notify(&self, key: &CircuitKey, from: CircuitState, to: CircuitState) {
+        if let Some(callback) = &self.config.on_state_change {
+            callback(&CircuitStateChange {
+                base_url: key.base_url.clone(),
+                model: key.model.clone(),
+                from,
+                to,
+            });
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<CircuitKey, Circuit>> {
+        self.circuits.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn config_mut(&mut self) -> &mut Config {
+        Arc::make_mut(&mut self.config)
+    }
+}
+
+impl Circuit {
+    /// Moves to `to`, resetting the counters, and returns the transition.
+    fn transition(&mut self, to: CircuitState) -> (CircuitState, CircuitState) {
+        let from = std::mem::replace(&mut self.state, to);
+        self.generation = self.generation.wrapping_add(1);
+        self.consecutive_failures = 0;
+        self.window.clear();
+        self.probing = false;
+        (from, to)
+    }
+}
+
+/// A request let through by a [`CircuitBreaker`].
+///
+/// Dropping the permit without recording an outcome, e.g. because the
+/// request was cancelled, frees the probe slot of a half-open circuit.
+pub(crate) struct Permit<'a> {
+    breaker: &'a CircuitBreaker,
+    key: CircuitKey,
+    probe: bool,
+    /// The circuit's generation when the request was let through.
+    generation: u64,
+    recorded: bool,
+}
+
+impl Permit<'_> {
+    pub(crate) fn record(self, failed: bool) {
+        self.record_at(failed, Instant::now());
+    }
+
+    fn record_at(mut self, failed: bool, now: Instant) {
+        self.recorded = true;
+        self.breaker.record(&self, failed, now);
+    }
+}
+
+impl Drop for Permit<'_> {
+    fn drop(&mut self) {
+        if self.probe && !self.recorded {
+            self.breaker.release(self);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key() -> CircuitKey {
+        model_key("gpt-test")
+    }
+
+    fn model_key(model: &str) -> CircuitKey {
+        CircuitKey {
+            base_url: "https://api.example.com/v1/".to_owned(),
+            model: Some(model.to_owned()),
+        }
+    }
+
+    fn state(breaker: &CircuitBreaker) -> CircuitState {
+        breaker.lock()[&key()].state
+    }
+
+    fn send(breaker: &CircuitBreaker, failed: bool, now: Instant) {
+        breaker
+            .acquire_at(key(), now)
+            .unwrap()
+            .record_at(failed, now);
+    }
+
+    fn fail(breaker: &CircuitBreaker, now: Instant) {
+        send(breaker, true, now);
+    }
+
+    #[test]
+    fn opens_after_consecutive_failures() {
+        let breaker = CircuitBreaker::new().failure_threshold(3);
+        let now = Instant::now();
+
+        fail(&breaker, now);
+        fail(&breaker, now);
+        send(&breaker, false, now);
+        fail(&breaker, now);
+        fail(&breaker, now);
+        assert_eq!(state(&breaker), CircuitState::Closed);
+
+        fail(&breaker, now);
+        assert_eq!(state(&breaker), CircuitState::Open);
+        let err = breaker.acquire_at(key(), now).err().unwrap();
+        assert!(matches!(err, Error::CircuitOpen { .. }), "got: {err:?}");
+    }
+
+    #[test]
+    fn opens_when_error_rate_is_exceeded() {
+        let breaker = CircuitBreaker::new()
+            .failure_threshold(10)
+            .error_rate_threshold(0.5)
+            .window_size(4);
+        let now = Instant::now();
+
+        for failed in [true, false, true] {
+            send(&breaker, failed, now);
+        }
+        assert_eq!(state(&breaker), CircuitState::Closed);
+        send(&breaker, false, now);
+        assert_eq!(state(&breaker), CircuitState::Closed);
+        fail(&breaker, now);
+        assert_eq!(state(&breaker), CircuitState::Open);
+    }
+
+    #[test]
+    fn half_open_circuit_lets_one_probe_through() {
+        let changes = Arc::new(Mutex::new(Vec::new()));
+        let recorder = changes.clone();
+        let breaker = CircuitBreaker::new()
+            .failure_threshold(1)
+            .open_duration(Duration::from_secs(10))
+            .on_state_change(move |change| recorder.lock().unwrap().push(change.to));
+        let now = Instant::now();
+
+        fail(&breaker, now);
+        assert!(
+            breaker
+                .acquire_at(key(), now + Duration::from_secs(9))
+                .is_err()
+        );
+
+        let later = now + Duration::from_secs(10);
+        let probe = breaker.acquire_at(key(), later).unwrap();
+        assert!(breaker.acquire_at(key(), later).is_err());
+        drop(probe);
+
+        // A dropped probe frees the slot; a failed one reopens the circuit.
+        breaker.acquire_at(key(), later).unwrap().record(true);
+        assert!(breaker.acquire_at(key(), later).is_err());
+
+        let latest = later + Duration::from_secs(10);
+        breaker.acquire_at(key(), latest).unwrap().record(false);
+        assert_eq!(state(&breaker), CircuitState::Closed);
+        assert_eq!(
+            *changes.lock().unwrap(),
+            [
+                CircuitState::Open,
+                CircuitState::HalfOpen,
+                CircuitState::Open,
+                CircuitState::HalfOpen,
+                CircuitState::Closed
+            ]
+        );
+    }
+
+    #[test]
+    fn only_the_probe_decides_a_half_open_circuit() {
+        let breaker = CircuitBreaker::new()
+            .failure_threshold(1)
+            .open_duration(Duration::from_secs(10));
+        let now = Instant::now();
+
+        // A slow request, let through while the circuit was still closed.
+        let stale = breaker.acquire_at(key(), now).unwrap();
+        fail(&breaker, now);
+
+        let later = now + Duration::from_secs(10);
+        let probe = breaker.acquire_at(key(), later).unwrap();
+        stale.record_at(false, later);
+        assert_eq!(state(&breaker), CircuitState::HalfOpen);
+        assert!(breaker.acquire_at(key(), later).is_err());
+
+        probe.record_at(true, later);
+        assert_eq!(state(&breaker), CircuitState::Open);
+    }
+
+    #[test]
+    fn forgets_idle_circuits_beyond_the_limit() {
+        let breaker = CircuitBreaker::new()
+            .failure_threshold(1)
+            .open_duration(Duration::from_secs(10))
+            .max_circuits(3);
+        let now = Instant::now();
+
+        fail(&breaker, now);
+        for model in ["a", "b"] {
+            breaker
+                .acquire_at(model_key(model), now)
+                .unwrap()
+                .record_at(false, now);
+        }
+        let soon = now + Duration::from_secs(1);
+        breaker.acquire_at(model_key("a"), soon).unwrap();
+
+        // The open circuit is kept, the least recently used closed one is not.
+        breaker.acquire_at(model_key("c"), soon).unwrap();
+        let circuits = breaker.lock();
+        assert_eq!(circuits.len(), 3);
+        assert!(!circuits.contains_key(&model_key("b")));
+        assert_eq!(circuits[&key()].state, CircuitState::Open);
+        drop(circuits);
+
+        // Closed and expired circuits unused for the open duration are dropped.
+        let later = soon + Duration::from_secs(10);
+        breaker.acquire_at(model_key("d"), later).unwrap();
+        let circuits = breaker.lock();
+        assert_eq!(circuits.len(), 1);
+        assert!(circuits.contains_key(&model_key("d")));
+    }
+}
@@ -0,0 +1,120 @@
+//! Cancellation of in-flight requests and streams.
+
+use crate::client::{Error, Result};
+
+use std::future::Future;
+use std::pin::pin;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::task::Poll;
+
+use tokio::sync::Notify;
+
+/// Aborts the requests and streams it is attached to.
+///
+/// Clones share the same state, so a token can be handed to a request and
+/// cancelled from another task. Cancelled requests fail with
+/// [`Error::Cancelled`]; cancelled streams yield it once and then end.
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken {
+    inner: Arc<Inner>,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    cancelled: AtomicBool,
+    /// Waiters deregister themselves when dropped, so a long-lived token
+    /// doesn't accumulate wakers of finished requests.
+    notify: Notify,
+}
+
+impl CancellationToken {
+    /// Creates a token that is not cancelled yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancels the token and everything it is attached to.
+    pub fn cancel(&self) {
+        self.inner.cancelled.store(true, Ordering::SeqCst);
+        self.inner.notify.notify_waiters();
+    }
+
+    /// Whether [`cancel`](Self::cancel) was called.
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Resolves once the token is cancelled.
+    pub async fn cancelled(&self) {
+        let mut notified = pin!(self.inner.notify.notified());
+        // Register before checking, so a concurrent `cancel` isn't missed.
+        notified.as_mut().enable();
+        if !self.is_cancelled() {
+            notified.await;
+        }
+    }
+}
+
+/// Runs `fut` to completion unless `token` is cancelled first.
+pub(crate) async fn or_cancel<T>(
+    token: Option<&CancellationToken>,
+    fut: impl Future<Output = Result<T>>,
+) -> Result<T> {
+    let Some(token) = token else {
+        return fut.await;
+    };
+    let mut cancelled = pin!(token.cancelled());
+    let mut fut = pin!(fut);
+    std::future::poll_fn(|cx| {
+        if cancelled.as_mut().poll(cx).is_ready() {
+            return Poll::Ready(Err(Error::Cancelled));
+        }
+        fut.as_mut().poll(cx)
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn or_cancel_aborts_pending_futures() {
+        let token = CancellationToken::new();
+        let canceller = token.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            canceller.cancel();
+        });
+
+        let result = or_cancel(Some(&token), std::future::pending::<Result<()>>()).await;
+        assert!(matches!(result, Err(Error::Cancelled)), "got: {result:?}");
+        assert!(token.is_cancelled());
+    }
+
+    #[tokio::test]
+    async fn or_cancel_passes_results_through() {
+        let token = CancellationToken::new();
+        let result = or_cancel(Some(&token), async { Ok(7) }).await;
+        assert_eq!(result.unwrap(), 7);
+        assert_eq!(or_cancel(None, async { Ok(8) }).await.unwrap(), 8);
+    }
+
+    #[tokio::test]
+    async fn cancelled_resolves_for_earlier_and_later_cancellation() {
+        let token = CancellationToken::new();
+        let waiter = tokio::spawn({
+            let token = token.clone();
+            async move { token.cancelled().await }
+        });
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        token.cancel();
+        waiter.await.unwrap();
+
+        // Already cancelled tokens resolve immediately.
+        token.cancelled().await;
+    }
+}
@@ -0,0 +1,460 @@
+//! Client configuration from environment variables and profile files.
+
+use crate::client::{ClientBuilder, Error, Result, RetryPolicy};
+
+use std::time::Duration;
+
+/// Environment variables read by [`ClientBuilder::from_env`].
+const ENV_API_KEY: &str = "OPENRESPONSES_API_KEY";
+const ENV_BASE_URL: &str = "OPENRESPONSES_BASE_URL";
+const ENV_TIMEOUT: &str = "OPENRESPONSES_TIMEOUT";
+const ENV_CONNECT_TIMEOUT: &str = "OPENRESPONSES_CONNECT_TIMEOUT";
+const ENV_USER_AGENT: &str = "OPENRESPONSES_USER_AGENT";
+const ENV_MAX_RETRIES: &str = "OPENRESPONSES_MAX_RETRIES";
+const ENV_RETRY_BASE_DELAY: &str = "OPENRESPONSES_RETRY_BASE_DELAY";
+const ENV_RETRY_MAX_DELAY: &str = "OPENRESPONSES_RETRY_MAX_DELAY";
+/// Selects the profile used by [`Profiles::selected`].
+#[cfg(feature = "profiles")]
+const ENV_PROFILE: &str = "OPENRESPONSES_PROFILE";
+
+/// Settings shared by environment variables and profiles.
+#[derive(Default)]
+struct Settings {
+    api_key: Option<String>,
+    base_url: Option<String>,
+    timeout: Option<f64>,
+    connect_timeout: Option<f64>,
+    user_agent: Option<String>,
+    max_retries: Option<u32>,
+    retry_base_delay: Option<f64>,
+    retry_max_delay: Option<f64>,
+}
+
+impl Settings {
+    /// Creates a builder; `source` names where the settings came from in errors.
+    fn into_builder(self, source: &str) -> Result<ClientBuilder> {
+        let api_key = self
+            .api_key
+            .ok_or_else(|| Error::Config(format!("{source}: missing API key")))?;
+        let base_url = self
+            .base_url
+            .ok_or_else(|| Error::Config(format!("{source}: missing base URL")))?;
+        let base_url = url::Url::parse(&base_url)
+            .map_err(|e| Error::Config(format!("{source}: invalid base URL {base_url:?}: {e}")))?;
+
+        let seconds = |field: &str, value: f64| {
+            Duration::try_from_secs_f64(value)
+                .map_err(|e| Error::Config(format!("{source}: invalid {field} {value}: {e}")))
+        };
+
+        let mut builder = ClientBuilder::new(api_key, base_url);
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(seconds("timeout", timeout)?);
+        }
+        if let Some(timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(seconds("connect timeout", timeout)?);
+        }
+        if let Some(user_agent) = self.user_agent {
+            builder = builder.user_agent(user_agent);
+        }
+        if let Some(max_retries) = self.max_retries {
+            let mut policy = RetryPolicy::default().max_attempts(max_retries.saturating_add(1));
+            if let Some(delay) = self.retry_base_delay {
+                policy = policy.base_delay(seconds("retry base delay", delay)?);
+            }
+            if let Some(delay) = self.retry_max_delay {
+                policy = policy.max_delay(seconds("retry max delay", delay)?);
+            }
+            builder = builder.retry_policy(policy);
+        }
+        Ok(builder)
+    }
+}
+
+/// Reads the client settings through `var`, which looks up a variable.
+pub(crate) fn from_vars(var: impl Fn(&str) -> Option<String>) -> Result<ClientBuilder> {
+    fn parse<T: std::str::FromStr>(name: &str, value: Option<String>) -> Result<Option<T>>
+    where
+        T::Err: std::fmt::Display,
+    {
+        value
+            .map(|value| {
+                value
+                    .trim()
+                    .parse()
+                    .map_err(|e| Error::Config(format!("{name}: invalid value {value:?}: {e}")))
+            })
+            .transpose()
+    }
+
+    let var = |name: &str| var(name).filter(|value| !value.is_empty());
+    Settings {
+        api_key: var(ENV_API_KEY),
+        base_url: var(ENV_BASE_URL),
+        timeout: parse(ENV_TIMEOUT, var(ENV_TIMEOUT))?,
+        connect_timeout: parse(ENV_CONNECT_TIMEOUT, var(ENV_CONNECT_TIMEOUT))?,
+        user_agent: var(ENV_USER_AGENT),
+        max_retries: parse(ENV_MAX_RETRIES, var(ENV_MAX_RETRIES))?,
+        retry_base_delay: parse(ENV_RETRY_BASE_DELAY, var(ENV_RETRY_BASE_DELAY))?,
+        retry_max_delay: parse(ENV_RETRY_MAX_DELAY, var(ENV_RETRY_MAX_DELAY))?,
+    }
+    .into_builder("environment")
+}
+
+/// A set of named client configurations, loaded from a TOML file.
+///
+/// ```toml
+/// # Used when OPENRESPONSES_PROFILE is not set.
+/// default = "local"
+///
+/// [profiles.local]
+/// base_url = "http://localhost:8080/v1"
+/// api_key = "local-key"
+///
+/// [profiles.prod]
+/// base_url = "https://api.example.com/v1"
+/// api_key_env = "PROD_API_KEY"
+/// timeout = 30
+/// max_retries = 2
+/// ```
+///
+/// This type is behind the Cargo feature `profiles`.
+#[cfg(feature = "profiles")]
+#[derive(Clone, Debug, Default, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Profiles {
+    default: Option<String>,
+    #[serde(default)]
+    profiles: std::collections::BTreeMap<String, Profile>,
+}
+
+/// A named client configuration in a [`Profiles`] file.
+///
+/// Durations are given in seconds.
+///
+/// This type is behind the Cargo feature `profiles`.
+#[cfg(feature = "profiles")]
+#[derive(Clone, Default, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Profile {
+    /// The base URL of the API.
+    pub base_url: String,
+    /// The API key.
+    pub api_key: Option<String>,
+    /// The environment variable to read the API key from, if `api_key` is not set.
+    pub api_key_env: Option<String>,
+    /// The request timeout.
+    pub timeout: Option<f64>,
+    /// The timeout for establishing connections.
+    pub connect_timeout: Option<f64>,
+    /// A custom user agent.
+    pub user_agent: Option<String>,
+    /// The number of retries after the first attempt. Requests are not retried if unset.
+    pub max_retries: Option<u32>,
+    /// The delay before the first retry.
+    pub retry_base_delay: Option<f64>,
+    /// The upper bound for the delay between attempts.
+    pub retry_max_delay: Option<f64>,
+}
+
+#[cfg(feature = "profiles")]
+impl std::fmt::Debug for Profile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Profile")
+            .field("base_url", &self.base_url)
+            .field("api_key_env", &self.api_key_env)
+            .field("timeout", &self.timeout)
+            .field("user_agent", &self.user_agent)
+            .field("max_retries", &self.max_retries)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "profiles")]
+impl Profiles {
+    /// Reads profiles from a TOML file.
+    pub fn load(path: impl AsRef<std::path::Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| Error::Config(format!("{}: {e}", path.display())))?;
+        Self::parse(&contents).map_err(|e| Error::Config(format!("{}: {e}", path.display())))
+    }
+
+    /// Parses profiles from a TOML string.
+    pub fn parse(toml: &str) -> Result<Self> {
+        toml::from_str(toml).map_err(|e| Error::Config(e.to_string()))
+    }
+
+    /// Returns the profile with the given name.
+    pub fn get(&self, name: &str) -> Option<&Profile> {
+        self.profiles.get(name)
+    }
+
+    /// Returns the names of all profiles, in alphabetical order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.profiles.keys().map(String::as_str)
+    }
+
+    /// Creates a [`ClientBuilder`] configured by the named profile.
+    pub fn builder(&self, name: &str) -> Result<ClientBuilder> {
+        self.builder_with_vars(name, |name| std::env::var(name).ok())
+    }
+
+    /// Like [`builder`](Self::builder), but looks up `api_key_env` through `var`.
+    pub(crate) fn builder_with_vars(
+        &self,
+        name: &str,
+        var: impl Fn(&str) -> Option<String>,
+    ) -> Result<ClientBuilder> {
+        let profile = self
+            .get(name)
+            .ok_or_else(|| Error::Config(format!("unknown profile {name:?}")))?;
+        let source = format!("profile {name:?}");
+
+        let api_key = match (&profile.api_key, &profile.api_key_env) {
+            (Some(key), _) => Some(key.clone()),
+            (None, Some(name)) => Some(var(name).ok_or_else(|| {
+                Error::Config(format!("{source}: environment variable {name} is not set"))
+            })?),
+            (None, None) => None,
+        };
+
+        Settings {
+            api_key,
+            base_url: Some(profile.base_url.clone()),
+            timeout: profile.timeout,
+            connect_timeout: profile.connect_timeout,
+            user_agent: profile.user_agent.clone(),
+            max_retries: profile.max_retries,
+            retry_base_delay: profile.retry_base_delay,
+            retry_max_delay: profile.retry_max_delay,
+        }
+        .into_builder(&source)
+    }
+
+    /// Creates a [`ClientBuilder`] for the profile named by the
+    /// `OPENRESPONSES_PROFILE` environment variable, or else for the file's
+    /// `default` profile.
+    pub fn selected(&self) -> Result<ClientBuilder> {
+        self.selected_with_vars(|name| std::env::var(name).ok())
+    }
+
+    /// Like [`selected`](Self::selected), but looks up variables through `var`.
+    pub(crate) fn selected_with_vars(
+        &self,
+        var: impl Fn(&str) -> Option<String>,
+    ) -> Result<ClientBuilder> {
+        let name = var(ENV_PROFILE)
+            .filter(|name| !name.is_empty())
+            .or_else(|| self.default.clone())
+            .ok_or_else(|| {
+                Error::Config(format!(
+                    "no profile selected: set {ENV_PROFILE} or `default`"
+                ))
+            })?;
+        self.builder_with_vars(&name, var)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::{Client, InMemoryTransport};
+
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    fn vars(pairs: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let map: HashMap<String, String> = pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        move |name| map.get(name).cloned()
+    }
+
+    fn message(err: Error) -> String {
+        match err {
+            Error::Config(message) => message,
+            other => panic!("expected Config error, got: {other:?}"),
+        }
+    }
+
+    /// Sends a request that always fails with `503` and returns what was sent.
+    async fn send_failing(builder: ClientBuilder) -> Vec<::http::Request<bytes::Bytes>> {
+        let transport = Arc::new(InMemoryTransport::json(
+            ::http::StatusCode::SERVICE_UNAVAILABLE,
+            serde_json::json!({}),
+        ));
+        let client = builder.transport(transport.clone()).build().unwrap();
+        let err = send(&client).await.unwrap_err();
+        assert_eq!(err.api_error().unwrap().status.as_u16(), 503);
+        transport.take_requests()
+    }
+
+    async fn send(client: &Client) -> Result<()> {
+        client
+            .responses()
+            .create_text("gpt-test", "hello")
+            .send()
+            .await
+            .map(|_| ())
+    }
+
+    #[tokio::test]
+    async fn reads_settings_from_variables() {
+        let requests = send_failing(
+            from_vars(vars(&[
+                ("OPENRESPONSES_API_KEY", "sk-test"),
+                ("OPENRESPONSES_BASE_URL", "http://localhost:8080/v1"),
+                ("OPENRESPONSES_USER_AGENT", "my-service/1.0"),
+                ("OPENRESPONSES_MAX_RETRIES", "2"),
+                ("OPENRESPONSES_RETRY_BASE_DELAY", "0.001"),
+            ]))
+            .unwrap(),
+        )
+        .await;
+
+        assert_eq!(requests.len(), 3);
+        let request = &requests[0];
+        assert_eq!(request.uri(), "http://localhost:8080/v1/responses");
+        assert_eq!(request.headers()["authorization"], "Bearer sk-test");
+        assert_eq!(request.headers()["user-agent"], "my-service/1.0");
+    }
+
+    #[tokio::test]
+    async fn reads_timeout_from_variables() {
+        // Accepts connections but never answers them.
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}/v1", listener.local_addr().unwrap());
+        let client = from_vars(vars(&[
+            ("OPENRESPONSES_API_KEY", "sk-test"),
+            ("OPENRESPONSES_BASE_URL", &base_url),
+            ("OPENRESPONSES_TIMEOUT", "0.05"),
+        ]))
+        .unwrap()
+        .build()
+        .unwrap();
+
+        let err = send(&client).await.unwrap_err();
+        assert!(
+            matches!(&err, Error::Reqwest(e) if e.is_timeout()),
+            "got: {err:?}"
+        );
+    }
+
+    #[test]
+    fn reports_missing_and_invalid_variables() {
+        let err = from_vars(vars(&[("OPENRESPONSES_BASE_URL", "http://localhost")])).unwrap_err();
+        assert_eq!(message(err), "environment: missing API key");
+
+        let err = from_vars(vars(&[
+            ("OPENRESPONSES_API_KEY", "sk-test"),
+            ("OPENRESPONSES_BASE_URL", "http://localhost"),
+            ("OPENRESPONSES_MAX_RETRIES", "many"),
+        ]))
+        .unwrap_err();
+        assert!(message(err).starts_with("OPENRESPONSES_MAX_RETRIES: invalid value \"many\""));
+
+        let err = from_vars(vars(&[
+            ("OPENRESPONSES_API_KEY", "sk-test"),
+            ("OPENRESPONSES_BASE_URL", "http://localhost"),
+            ("OPENRESPONSES_TIMEOUT", "-1"),
+        ]))
+        .unwrap_err();
+        assert!(message(err).starts_with("environment: invalid timeout -1"));
+    }
+
+    #[cfg(feature = "profiles")]
+    #[tokio::test]
+    async fn builds_clients_from_profiles() {
+        let profiles = Profiles::parse(
+            r#"
+            default = "local"
+
+            [profiles.local]
+            base_url = "http://localhost:8080/v1"
+            api_key = "local-key"
+            timeout = 30
+
+            [profiles.staging]
+            base_url = "https://staging.example.com/v1"
+            api_key = "staging-key"
+            max_retries = 1
+            retry_base_delay = 0.001
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(profiles.names().collect::<Vec<_>>(), ["local", "staging"]);
+        assert_eq!(profiles.get("local").unwrap().timeout, Some(30.0));
+
+        let requests = send_failing(profiles.builder("staging").unwrap()).await;
+        assert_eq!(requests.len(), 2);
+        assert_eq!(
+            requests[0].uri(),
+            "https://staging.example.com/v1/responses"
+        );
+        assert_eq!(requests[0].headers()["authorization"], "Bearer staging-key");
+
+        let err = profiles.builder("prod").unwrap_err();
+        assert_eq!(message(err), "unknown profile \"prod\"");
+    }
+
+    #[cfg(feature = "profiles")]
+    #[tokio::test]
+    async fn selects_profile_from_variables() {
+        let profiles = Profiles::parse(
+            r#"
+            default = "local"
+
+            [profiles.local]
+            base_url = "http://localhost:8080/v1"
+            api_key = "local-key"
+
+            [profiles.staging]
+            base_url = "https://staging.example.com/v1"
+            api_key_env = "STAGING_KEY"
+            "#,
+        )
+        .unwrap();
+
+        let builder = profiles.selected_with_vars(vars(&[])).unwrap();
+        let requests = send_failing(builder).await;
+        assert_eq!(requests[0].uri(), "http://localhost:8080/v1/responses");
+
+        let builder = profiles
+            .selected_with_vars(vars(&[
+                ("OPENRESPONSES_PROFILE", "staging"),
+                ("STAGING_KEY", "staging-key"),
+            ]))
+            .unwrap();
+        let requests = send_failing(builder).await;
+        assert_eq!(
+            requests[0].uri(),
+            "https://staging.example.com/v1/responses"
+        );
+        assert_eq!(requests[0].headers()["authorization"], "Bearer staging-key");
+
+        let err = profiles
+            .selected_with_vars(vars(&[("OPENRESPONSES_PROFILE", "staging")]))
+            .unwrap_err();
+        assert_eq!(
+            message(err),
+            "profile \"staging\": environment variable STAGING_KEY is not set"
+        );
+    }
+
+    #[cfg(feature = "profiles")]
+    #[test]
+    fn rejects_unknown_profile_fields() {
+        let err = Profiles::parse(
+            r#"
+            [profiles.local]
+            base_url = "http://localhost:8080/v1"
+            api_kye = "typo"
+            "#,
+        )
+        .unwrap_err();
+        assert!(message(err).contains("api_kye"));
+    }
+}
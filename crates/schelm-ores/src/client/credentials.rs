@@ -0,0 +1,429 @@
+//! Credentials attached to every request.
+//!
+//! A [`Client`](crate::client::Client) asks its [`CredentialProvider`] for a
+//! [`Credential`] before each attempt, so keys can rotate and tokens can be
+//! refreshed without rebuilding the client. The API key passed to
+//! [`Client::builder`](crate::client::Client::builder) is used as a
+//! [`StaticCredentials`] bearer token unless
+//! [`ClientBuilder::credentials`](crate::client::ClientBuilder::credentials)
+//! sets another provider.
+
+use crate::client::{BoxFuture, Error, Result};
+
+use reqwest::header::{AUTHORIZATION, HeaderMap, HeaderName, HeaderValue};
+
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
+
+/// Supplies the credential for each request.
+///
+/// Implementations are called once per attempt, including retries, so they
+/// should cache credentials that are expensive to obtain.
+pub trait CredentialProvider: Send + Sync {
+    /// Returns the credential to attach to the next request.
+    fn credential(&self) -> BoxFuture<'_, Result<Credential>>;
+}
+
+impl<T: CredentialProvider + ?Sized> CredentialProvider for Arc<T> {
+    fn credential(&self) -> BoxFuture<'_, Result<Credential>> {
+        (**self).credential()
+    }
+}
+
+/// A secret, together with the header it is sent in.
+///
+/// The header value is marked as sensitive and is not printed by [`Debug`].
+#[derive(Clone, PartialEq, Eq)]
+pub struct Credential {
+    name: HeaderName,
+    value: HeaderValue,
+}
+
+impl Credential {
+    /// An `Authorization: Bearer <token>` credential.
+    pub fn bearer(token: impl AsRef<str>) -> Result<Self> {
+        Self::header(AUTHORIZATION, format!("Bearer {}", token.as_ref()))
+    }
+
+    /// An Azure-style `api-key: <key>` credential.
+    pub fn api_key(key: impl AsRef<str>) -> Result<Self> {
+        Self::header(HeaderName::from_static("api-key"), key)
+    }
+
+    /// A credential sent verbatim in a custom header.
+    pub fn header(name: HeaderName, value: impl AsRef<str>) -> Result<Self> {
+        let mut value = HeaderValue::from_str(value.as_ref())
+            .map_err(|e| Error::InvalidHeaderValue(e.to_string()))?;
+        value.set_sensitive(true);
+        Ok(Self { name, value })
+    }
+
+    /// The header the credential is sent in.
+    pub fn header_name(&self) -> &HeaderName {
+        &self.name
+    }
+
+    /// Adds the credential to `headers`, unless they already set its header.
+    pub(crate) fn apply(self, headers: &mut HeaderMap) {
+        if !headers.contains_key(&self.name) {
+            headers.insert(self.name, self.value);
+        }
+    }
+}
+
+impl std::fmt::Debug for Credential {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Credential")
+            .field("name", &self.name)
+            .finish_non_exhaustive()
+    }
+}
+
+/// How a secret read by [`EnvCredentials`] or [`FileCredentials`] is sent.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum AuthScheme {
+    /// `Authorization: Bearer <secret>`.
+    #[default]
+    Bearer,
+    /// Azure-style `api-key: <secret>`.
+    ApiKey,
+    /// The secret verbatim in the given header.
+    Header(HeaderName),
+}
+
+impl AuthScheme {
+    /// Creates a credential for `secret` using this scheme.
+    pub fn credential(&self, secret: &str) -> Result<Credential> {
+        match self {
+            AuthScheme::Bearer => Credential::bearer(secret),
+            AuthScheme::ApiKey => Credential::api_key(secret),
+            AuthScheme::Header(name) => Credential::header(name.clone(), secret),
+        }
+    }
+}
+
+/// Always returns the same credential.
+#[derive(Clone, Debug)]
+pub struct StaticCredentials(Credential);
+
+impl StaticCredentials {
+    /// Returns `credential` for every request.
+    pub fn new(credential: Credential) -> Self {
+        Self(credential)
+    }
+}
+
+impl CredentialProvider for StaticCredentials {
+    fn credential(&self) -> BoxFuture<'_, Result<Credential>> {
+        Box::pin(std::future::ready(Ok(self.0.clone())))
+    }
+}
+
+/// Reads the secret from an environment variable on every request.
+#[derive(Clone, Debug)]
+pub struct EnvCredentials {
+    var: String,
+    scheme: AuthScheme,
+}
+
+impl EnvCredentials {
+    /// Reads the secret from the environment variable `var` and sends it as a
+    /// bearer token.
+    pub fn new(var: impl Into<String>) -> Self {
+        Self {
+            var: var.into(),
+            scheme: AuthScheme::Bearer,
+        }
+    }
+
+    /// Sets how the secret is sent.
+    pub fn scheme(mut self, scheme: AuthScheme) -> Self {
+        self.scheme = scheme;
+        self
+    }
+
+    /// Reads the secret through `var`, which looks up a variable.
+    fn load(
+        &self,
+        var: impl FnOnce(&str) -> std::result::Result<String, std::env::VarError>,
+    ) -> Result<Credential> {
+        match var(&self.var) {
+            Ok(secret) => self.scheme.credential(secret.trim()),
+            Err(e) => Err(Error::Credentials(
+                format!("environment variable {}: {e}", self.var).into(),
+            )),
+        }
+    }
+}
+
+impl CredentialProvider for EnvCredentials {
+    fn credential(&self) -> BoxFuture<'_, Result<Credential>> {
+        Box::pin(std::future::ready(self.load(|var| std::env::var(var))))
+    }
+}
+
+/// Reads the secret from a file and reloads it whenever the file changes.
+///
+/// The file's modification time and size are checked before each request;
+/// the file is only read again when either changed. Surrounding whitespace,
+/// such as a trailing newline, is ignored.
+#[derive(Debug)]
+pub struct FileCredentials {
+    path: PathBuf,
+    scheme: AuthScheme,
+    cached: Mutex<Option<(FileStamp, Credential)>>,
+}
+
+type FileStamp = (Option<SystemTime>, u64);
+
+impl FileCredentials {
+    /// Reads the secret from `path` and sends it as a bearer token.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            scheme: AuthScheme::Bearer,
+            cached: Mutex::new(None),
+        }
+    }
+
+    /// Sets how the secret is sent.
+    pub fn scheme(mut self, scheme: AuthScheme) -> Self {
+        self.scheme = scheme;
+        self
+    }
+
+    async fn load(&self) -> Result<Credential> {
+        let read_error =
+            |e: std::io::Error| Error::Credentials(format!("{}: {e}", self.path.display()).into());
+
+        let metadata = tokio::fs::metadata(&self.path).await.map_err(read_error)?;
+        let stamp = (metadata.modified().ok(), metadata.len());
+
+        let cached = self
+            .cached()
+            .clone()
+            .filter(|(cached_stamp, _)| *cached_stamp == stamp);
+        if let Some((_, credential)) = cached {
+            return Ok(credential);
+        }
+
+        let secret = tokio::fs::read_to_string(&self.path)
+            .await
+            .map_err(read_error)?;
+        let credential = self.scheme.credential(secret.trim())?;
+        *self.cached() = Some((stamp, credential.clone()));
+        Ok(credential)
+    }
+
+    fn cached(&self) -> std::sync::MutexGuard<'_, Option<(FileStamp, Credential)>> {
+        self.cached.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl CredentialProvider for FileCredentials {
+    fn credential(&self) -> BoxFuture<'_, Result<Credential>> {
+        Box::pin(self.load())
+    }
+}
+
+/// A short-lived credential, as returned by the fetch function of
+/// [`RefreshingCredentials`].
+#[derive(Clone, Debug)]
+pub struct AccessToken {
+    /// The credential to send.
+    pub credential: Credential,
+    /// When the credential stops being valid.
+    pub expires_at: Instant,
+}
+
+impl AccessToken {
+    /// A credential that is valid for `expires_in` from now.
+    pub fn new(credential: Credential, expires_in: Duration) -> Self {
+        Self {
+            credential,
+            expires_at: Instant::now() + expires_in,
+        }
+    }
+}
+
+type FetchToken = dyn Fn() -> BoxFuture<'static, Result<AccessToken>> + Send + Sync;
+
+/// Caches a short-lived token, such as an OAuth access token, and fetches a
+/// new one shortly before it expires.
+///
+/// Concurrent requests wait for a single refresh. A failed refresh is
+/// returned to the request that triggered it and retried on the next request.
+pub struct RefreshingCredentials {
+    fetch: Box<FetchToken>,
+    refresh_before: Duration,
+    cached: tokio::sync::Mutex<Option<AccessToken>>,
+}
+
+impl RefreshingCredentials {
+    /// Calls `fetch` to obtain a token whenever the cached one is about to expire.
+    pub fn new<F, Fut>(fetch: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<AccessToken>> + Send + 'static,
+    {
+        Self {
+            fetch: Box::new(move || Box::pin(fetch())),
+            refresh_before: Duration::from_secs(60),
+            cached: tokio::sync::Mutex::new(None),
+        }
+    }
+
+    /// Sets how long before expiry a token is refreshed. Defaults to 60 seconds.
+    pub fn refresh_before(mut self, refresh_before: Duration) -> Self {
+        self.refresh_before = refresh_before;
+        self
+    }
+}
+
+impl std::fmt::Debug for RefreshingCredentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RefreshingCredentials")
+            .field("refresh_before", &self.refresh_before)
+            .finish_non_exhaustive()
+    }
+}
+
+impl CredentialProvider for RefreshingCredentials {
+    fn credential(&self) -> BoxFuture<'_, Result<Credential>> {
+        Box::pin(async move {
+            let mut cached = self.cached.lock().await;
+            if let Some(token) = &*cached
+                && Instant::now() + self.refresh_before < token.expires_at
+            {
+                return Ok(token.credential.clone());
+            }
+
+            let token = (self.fetch)().await?;
+            let credential = token.credential.clone();
+            *cached = Some(token);
+            Ok(credential)
+        })
+    }
+}
+
+/// The credential provider a client was built with.
+#[derive(Clone)]
+pub(crate) struct SharedCredentials(Arc<dyn CredentialProvider>);
+
+impl SharedCredentials {
+    pub(crate) fn new(provider: impl CredentialProvider + 'static) -> Self {
+        Self(Arc::new(provider))
+    }
+
+    pub(crate) fn credential(&self) -> BoxFuture<'_, Result<Credential>> {
+        self.0.credential()
+    }
+}
+
+impl std::fmt::Debug for SharedCredentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SharedCredentials").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn debug_hides_secret() {
+        let credential = Credential::bearer("sk-secret").unwrap();
+        let debug = format!("{credential:?}");
+        assert!(debug.contains("authorization"), "{debug}");
+        assert!(!debug.contains("sk-secret"), "{debug}");
+    }
+
+    #[test]
+    fn schemes_pick_the_header() {
+        let bearer = AuthScheme::Bearer.credential("k").unwrap();
+        assert_eq!(bearer.value, "Bearer k");
+        let api_key = AuthScheme::ApiKey.credential("k").unwrap();
+        assert_eq!(api_key.header_name(), "api-key");
+        assert_eq!(api_key.value, "k");
+        let custom = AuthScheme::Header(HeaderName::from_static("x-token"))
+            .credential("k")
+            .unwrap();
+        assert_eq!(custom.header_name(), "x-token");
+    }
+
+    #[test]
+    fn apply_keeps_existing_header() {
+        let mut headers = HeaderMap::new();
+        headers.insert(AUTHORIZATION, HeaderValue::from_static("Bearer mine"));
+        Credential::bearer("theirs").unwrap().apply(&mut headers);
+        assert_eq!(headers[AUTHORIZATION], "Bearer mine");
+    }
+
+    #[test]
+    fn env_credentials_read_the_variable() {
+        let provider = EnvCredentials::new("ROTATING_KEY").scheme(AuthScheme::ApiKey);
+        let lookup = |value: &'static str| {
+            move |var: &str| {
+                assert_eq!(var, "ROTATING_KEY");
+                Ok(value.to_owned())
+            }
+        };
+
+        assert_eq!(provider.load(lookup("first\n")).unwrap().value, "first");
+        assert_eq!(provider.load(lookup("second")).unwrap().value, "second");
+        let err = provider
+            .load(|_| Err(std::env::VarError::NotPresent))
+            .unwrap_err();
+        assert!(matches!(err, Error::Credentials(_)), "got: {err:?}");
+    }
+
+    #[tokio::test]
+    async fn file_credentials_reload_on_change() {
+        let path = std::env::temp_dir().join(format!(
+            "schelm-ores-credentials-{}-{:?}",
+            std::process::id(),
+            std::thread::current().id()
+        ));
+        std::fs::write(&path, "first\n").unwrap();
+        let provider = FileCredentials::new(&path).scheme(AuthScheme::ApiKey);
+
+        let credential = provider.credential().await.unwrap();
+        assert_eq!(credential.value, "first");
+
+        // A different length is detected even if the modification time
+        // doesn't change within the file system's resolution.
+        std::fs::write(&path, "second-key").unwrap();
+        let credential = provider.credential().await.unwrap();
+        assert_eq!(credential.value, "second-key");
+
+        std::fs::remove_file(&path).unwrap();
+        let err = provider.credential().await.unwrap_err();
+        assert!(matches!(err, Error::Credentials(_)), "got: {err:?}");
+    }
+
+    #[tokio::test]
+    async fn refreshing_credentials_cache_until_expiry() {
+        let fetches = Arc::new(AtomicUsize::new(0));
+        let counter = fetches.clone();
+        let provider = RefreshingCredentials::new(move || {
+            let n = counter.fetch_add(1, Ordering::SeqCst);
+            async move {
+                // The first token is already within the refresh window.
+                let expires_in = if n == 0 { 30 } else { 3600 };
+                Ok(AccessToken::new(
+                    Credential::bearer(format!("token-{n}"))?,
+                    Duration::from_secs(expires_in),
+                ))
+            }
+        });
+
+        assert_eq!(provider.credential().await.unwrap().value, "Bearer token-0");
+        assert_eq!(provider.credential().await.unwrap().value, "Bearer token-1");
+        assert_eq!(provider.credential().await.unwrap().value, "Bearer token-1");
+        assert_eq!(fetches.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,153 @@
+use crate::client::http;
+use crate::models;
+
+use crate::client::{Client, ListRequestBuilder, Page, Result};
+
+/// Conversations endpoint group.
+#[derive(Clone, Copy, Debug)]
+pub struct Conversations<'a> {
+    client: &'a Client,
+}
+
+impl<'a> Conversations<'a> {
+    pub(crate) fn new(client: &'a Client) -> Self {
+        Self { client }
+    }
+
+    /// Creates a new conversation.
+    pub fn create(&self) -> CreateConversationRequestBuilder<'a> {
+        CreateConversationRequestBuilder {
+            client: self.client,
+            body: models::CreateConversationBody::default(),
+        }
+    }
+
+    /// Retrieves a conversation by its ID.
+    ///
+    /// Returns [`Error::NotFound`](crate::client::Error::NotFound) if no
+    /// conversation with this ID exists.
+    pub async fn retrieve(&self, id: impl AsRef<str>) -> Result<models::ConversationResource> {
+        let url = self.client.resource_url(&["conversations", id.as_ref()])?;
+
+        let resp = self.client.execute(self.client.get(url)).await?;
+
+        http::resource_json(resp).await
+    }
+
+    /// Replaces the metadata of a conversation.
+    pub async fn update(
+        &self,
+        id: impl AsRef<str>,
+        metadata: models::MetadataParam,
+    ) -> Result<models::ConversationResource> {
+        let url = self.client.resource_url(&["conversations", id.as_ref()])?;
+        let body = models::UpdateConversationBody { metadata };
+
+        let resp = self
+            .client
+            .execute(self.client.post(url).json(&body))
+            .await?;
+
+        http::resource_json(resp).await
+    }
+
+    /// Deletes a conversation. Items in the conversation are not deleted.
+    pub async fn delete(&self, id: impl AsRef<str>) -> Result<models::DeletedResource> {
+        let url = self.client.resource_url(&["conversations", id.as_ref()])?;
+
+        let resp = self.client.execute(self.client.delete(url)).await?;
+
+        http::resource_json(resp).await
+    }
+
+    /// Access the items of a conversation.
+    pub fn items(&self, conversation_id: impl Into<String>) -> ConversationItems<'a> {
+        ConversationItems {
+            client: self.client,
+            conversation_id: conversation_id.into(),
+        }
+    }
+}
+
+/// Request builder for `POST /conversations`.
+#[derive(Debug)]
+pub struct CreateConversationRequestBuilder<'a> {
+    client: &'a Client,
+    body: models::CreateConversationBody,
+}
+
+impl<'a> CreateConversationRequestBuilder<'a> {
+    /// Sets the initial items of the conversation. Up to 20 items.
+    pub fn items(mut self, items: Vec<models::ItemParam>) -> Self {
+        self.body.items = Some(items);
+        self
+    }
+
+    /// Attaches up to 16 key-value pairs to the conversation.
+    pub fn metadata(mut self, metadata: models::MetadataParam) -> Self {
+        self.body.metadata = Some(metadata);
+        self
+    }
+
+    /// Sends the request and returns the created conversation.
+    pub async fn send(self) -> Result<models::ConversationResource> {
+        let url = self.client.endpoint_url("conversations")?;
+
+        let resp = self
+            .client
+            .execute(self.client.post(url).json(&self.body))
+            .await?;
+
+        http::json(resp).await
+    }
+}
+
+/// Items of a single conversation, created via [`Conversations::items`].
+#[derive(Clone, Debug)]
+pub struct ConversationItems<'a> {
+    client: &'a Client,
+    conversation_id: String,
+}
+
+impl<'a> ConversationItems<'a> {
+    /// Lists the items of the conversation.
+    pub fn list(&self) -> ListRequestBuilder<'a, models::ItemField> {
+        ListRequestBuilder::new(
+            self.client,
+            &["conversations", &self.conversation_id, "items"],
+        )
+    }
+
+    /// Adds items to the conversation. Up to 20 items at a time.
+    ///
+    /// Returns the added items as they were stored.
+    pub async fn add(&self, items: Vec<models::ItemParam>) -> Result<Page<models::ItemField>> {
+        let url = self
+            .client
+            .resource_url(&["conversations", &self.conversation_id, "items"])?;
+        let body = models::AddConversationItemsBody { items };
+
+        let resp = self
+            .client
+            .execute(self.client.post(url).json(&body))
+            .await?;
+
+        http::resource_json(resp).await
+    }
+
+    /// Deletes an item from the conversation.
+    ///
+    /// Returns the conversation the item was deleted from.
+    pub async fn delete(&self, item_id: impl AsRef<str>) -> Result<models::ConversationResource> {
+        let url = self.client.resource_url(&[
+            "conversations",
+            &self.conversation_id,
+            "items",
+            item_id.as_ref(),
+        ])?;
+
+        let resp = self.client.execute(self.client.delete(url)).await?;
+
+        http::resource_json(resp).await
+    }
+}
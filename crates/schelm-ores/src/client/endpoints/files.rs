@@ -0,0 +1,136 @@
+use crate::client::http;
+use crate::models;
+
+use crate::client::{Client, ListRequestBuilder, Result};
+
+/// Files endpoint group.
+#[derive(Clone, Copy, Debug)]
+pub struct Files<'a> {
+    client: &'a Client,
+}
+
+impl<'a> Files<'a> {
+    pub(crate) fn new(client: &'a Client) -> Self {
+        Self { client }
+    }
+
+    /// Uploads a file.
+    ///
+    /// The returned file ID can be referenced from file and image inputs via
+    /// their `file_id` field, so large files only need to be uploaded once.
+    pub fn upload(
+        &self,
+        filename: impl Into<String>,
+        data: impl Into<bytes::Bytes>,
+        purpose: models::FilePurposeEnum,
+    ) -> UploadFileRequestBuilder<'a> {
+        UploadFileRequestBuilder {
+            client: self.client,
+            filename: filename.into(),
+            data: data.into(),
+            purpose,
+            content_type: None,
+        }
+    }
+
+    /// Lists the uploaded files.
+    pub fn list(&self) -> ListRequestBuilder<'a, models::FileResource> {
+        ListRequestBuilder::new(self.client, &["files"])
+    }
+
+    /// Retrieves the metadata of an uploaded file.
+    pub async fn retrieve(&self, id: impl AsRef<str>) -> Result<models::FileResource> {
+        let url = self.client.resource_url(&["files", id.as_ref()])?;
+
+        let resp = self.client.execute(self.client.get(url)).await?;
+
+        http::resource_json(resp).await
+    }
+
+    /// Downloads the contents of an uploaded file.
+    pub async fn content(&self, id: impl AsRef<str>) -> Result<bytes::Bytes> {
+        let url = self
+            .client
+            .resource_url(&["files", id.as_ref(), "content"])?;
+
+        let resp = self.client.execute(self.client.get(url)).await?;
+
+        if !resp.status().is_success() {
+            return Err(http::read_resource_error_body(resp).await?);
+        }
+
+        resp.bytes().await
+    }
+
+    /// Deletes an uploaded file.
+    pub async fn delete(&self, id: impl AsRef<str>) -> Result<models::DeletedResource> {
+        let url = self.client.resource_url(&["files", id.as_ref()])?;
+
+        let resp = self.client.execute(self.client.delete(url)).await?;
+
+        http::resource_json(resp).await
+    }
+}
+
+impl ListRequestBuilder<'_, models::FileResource> {
+    /// Only return files with the given purpose.
+    pub fn purpose(self, purpose: models::FilePurposeEnum) -> Self {
+        self.query_pair("purpose", purpose_value(&purpose))
+    }
+}
+
+/// Request builder for `POST /files`.
+#[derive(Debug)]
+pub struct UploadFileRequestBuilder<'a> {
+    client: &'a Client,
+    filename: String,
+    data: bytes::Bytes,
+    purpose: models::FilePurposeEnum,
+    content_type: Option<String>,
+}
+
+impl<'a> UploadFileRequestBuilder<'a> {
+    /// Sets the MIME type of the file. Defaults to `application/octet-stream`.
+    pub fn content_type(mut self, content_type: impl Into<String>) -> Self {
+        self.content_type = Some(content_type.into());
+        self
+    }
+
+    /// Uploads the file and returns its metadata.
+    pub async fn send(self) -> Result<models::FileResource> {
+        let url = self.client.endpoint_url("files")?;
+
+        let form = http::Multipart::new()
+            .text("purpose", purpose_value(&self.purpose))
+            .file(
+                "file",
+                &self.filename,
+                self.content_type
+                    .as_deref()
+                    .unwrap_or("application/octet-stream"),
+                &self.data,
+            )?;
+
+        let request = self
+            .client
+            .post(url)
+            .header(reqwest::header::CONTENT_TYPE, form.content_type())
+            .body(form.finish());
+
+        let resp = self.client.execute(request).await?;
+
+        http::json(resp).await
+    }
+}
+
+/// Returns the wire value of a [`FilePurposeEnum`](models::FilePurposeEnum).
+fn purpose_value(purpose: &models::FilePurposeEnum) -> &'static str {
+    match purpose {
+        models::FilePurposeEnum::UserData => "user_data",
+        models::FilePurposeEnum::Vision => "vision",
+        models::FilePurposeEnum::Assistants => "assistants",
+        models::FilePurposeEnum::Batch => "batch",
+        models::FilePurposeEnum::FineTune => "fine-tune",
+        models::FilePurposeEnum::Evals => "evals",
+    }
+}
@@ -1 +1,4 @@
+pub mod conversations;
+pub mod files;
+pub mod models;
 pub mod responses;
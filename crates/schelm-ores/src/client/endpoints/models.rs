@@ -0,0 +1,38 @@
+use crate::client::http;
+use crate::models;
+
+use crate::client::{Client, Page, Result};
+
+/// Models endpoint group.
+#[derive(Clone, Copy, Debug)]
+pub struct Models<'a> {
+    client: &'a Client,
+}
+
+impl<'a> Models<'a> {
+    pub(crate) fn new(client: &'a Client) -> Self {
+        Self { client }
+    }
+
+    /// Lists the models available on the server.
+    pub async fn list(&self) -> Result<Page<models::Model>> {
+        let url = self.client.endpoint_url("models")?;
+
+        let resp = self.client.execute(self.client.get(url)).await?;
+
+        http::json(resp).await
+    }
+
+    /// Retrieves a model by its ID.
+    ///
+    /// Returns [`Error::NotFound`](crate::client::Error::NotFound) if the server
+    /// does not offer this model, which makes it suitable for validating model
+    /// names at startup.
+    pub async fn retrieve(&self, id: impl AsRef<str>) -> Result<models::Model> {
+        let url = self.client.resource_url(&["models", id.as_ref()])?;
+
+        let resp = self.client.execute(self.client.get(url)).await?;
+
+        http::resource_json(resp).await
+    }
+}
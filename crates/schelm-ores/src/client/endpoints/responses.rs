@@ -1,7 +1,14 @@
-use crate::client::{http, sse};
+use crate::client::{cancel, http, sse, validation};
 use crate::models;
 
-use crate::client::{Client, Result};
+use crate::client::{
+    CancellationToken, Client, Error, ListRequestBuilder, ResponseMeta, Result, WithMeta,
+};
+
+use std::time::{Duration, Instant};
+
+/// Statuses after which a response will no longer change.
+const TERMINAL_STATUSES: &[&str] = &["completed", "failed", "cancelled", "incomplete"];
 
 /// Responses endpoint group.
 #[derive(Clone, Copy, Debug)]
@@ -22,12 +29,13 @@ impl<'a> Responses<'a> {
         model: impl Into<String>,
         input: models::CreateResponseInput,
     ) -> CreateResponseRequestBuilder<'a> {
-        CreateResponseRequestBuilder {
-            client: self.client,
-            body: models::CreateResponseBody {
+        CreateResponseRequestBuilder::from_body(
+            self.client,
+            models::CreateResponseBody {
                 model: Some(model.into()),
                 input: Some(input),
                 previous_response_id: None,
+                conversation: None,
                 include: None,
                 tools: None,
                 tool_choice: None,
@@ -52,7 +60,7 @@ impl<'a> Responses<'a> {
                 service_tier: None,
                 top_logprobs: None,
             },
-        }
+        )
     }
 
     /// Convenience helper to create a response from a single user text input.
@@ -63,6 +71,93 @@ impl<'a> Responses<'a> {
     ) -> CreateResponseRequestBuilder<'a> {
         self.create(model, models::CreateResponseInput::String(text.into()))
     }
+
+    /// Counts the input tokens of a response request without generating output.
+    ///
+    /// Takes the same [`CreateResponseRequestBuilder`] that would be used to send
+    /// the request, so the counted input can't drift from the real one.
+    pub async fn input_tokens(
+        &self,
+        request: &CreateResponseRequestBuilder<'_>,
+    ) -> Result<models::InputTokensResource> {
+        request.validate()?;
+        let body = models::InputTokensBody::from(&request.body);
+
+        let resp = self
+            .client
+            .execute(request.request("responses/input_tokens", &body, None)?)
+            .await?;
+
+        http::json(resp).await
+    }
+
+    /// Retrieves a stored response by its ID.
+    ///
+    /// Only responses created with `store=true` can be retrieved.
+    pub fn retrieve(&self, id: impl Into<String>) -> RetrieveResponseRequestBuilder<'a> {
+        RetrieveResponseRequestBuilder {
+            client: self.client,
+            id: id.into(),
+            include: Vec::new(),
+            starting_after: None,
+        }
+    }
+
+    /// Cancels a response that was created with `background=true`.
+    ///
+    /// Returns the response resource in its updated state.
+    pub async fn cancel(&self, id: impl AsRef<str>) -> Result<models::ResponseResource> {
+        let url = self
+            .client
+            .resource_url(&["responses", id.as_ref(), "cancel"])?;
+
+        let resp = self.client.execute(self.client.post(url)).await?;
+
+        http::resource_json(resp).await
+    }
+
+    /// Deletes a stored response.
+    ///
+    /// Returns [`Error::NotFound`] if no response with this ID exists.
+    pub async fn delete(&self, id: impl AsRef<str>) -> Result<models::DeletedResource> {
+        let url = self.client.resource_url(&["responses", id.as_ref()])?;
+
+        let resp = self.client.execute(self.client.delete(url)).await?;
+
+        http::resource_json(resp).await
+    }
+
+    /// Lists the input items that were used to generate a stored response.
+    ///
+    /// Returns a [`ListRequestBuilder`] that fetches a single page via `send()`,
+    /// or every page lazily via `into_stream()`.
+    pub fn input_items(&self, id: impl AsRef<str>) -> ListRequestBuilder<'a, models::ItemField> {
+        ListRequestBuilder::new(self.client, &["responses", id.as_ref(), "input_items"])
+    }
+
+    /// Polls a stored response until its status is `completed`, `failed`,
+    /// `cancelled` or `incomplete`.
+    ///
+    /// Intended for responses created with `background=true`.
+    pub fn wait_until_terminal(&self, id: impl Into<String>) -> WaitUntilTerminalBuilder<'a> {
+        WaitUntilTerminalBuilder {
+            client: self.client,
+            id: id.into(),
+            poll_interval: Duration::from_millis(500),
+            max_poll_interval: Duration::from_secs(10),
+            backoff_multiplier: 2.0,
+            timeout: None,
+        }
+    }
+
+    /// Returns the create endpoint as a [`tower::Service`] that owns a clone of
+    /// the client.
+    ///
+    /// This method is behind the Cargo feature `tower`.
+    #[cfg(feature = "tower")]
+    pub fn service(&self) -> crate::client::ResponsesService {
+        crate::client::ResponsesService::new(self.client.clone())
+    }
 }
 
 /// Request builder for `POST /responses`.
@@ -70,9 +165,30 @@ impl<'a> Responses<'a> {
 pub struct CreateResponseRequestBuilder<'a> {
     client: &'a Client,
     body: models::CreateResponseBody,
+    extra_body: serde_json::Map<String, serde_json::Value>,
+    extra_headers: Vec<(String, String)>,
+    extra_query: Vec<(String, String)>,
+    timeout: Option<Duration>,
+    cancel: Option<CancellationToken>,
+    idempotency_key: Option<String>,
+    fallbacks: Vec<ModelFallback>,
 }
 
 impl<'a> CreateResponseRequestBuilder<'a> {
+    pub(crate) fn from_body(client: &'a Client, body: models::CreateResponseBody) -> Self {
+        Self {
+            client,
+            body,
+            extra_body: serde_json::Map::new(),
+            extra_headers: Vec::new(),
+            extra_query: Vec::new(),
+            timeout: None,
+            cancel: None,
+            idempotency_key: None,
+            fallbacks: Vec::new(),
+        }
+    }
+
     pub fn instructions(mut self, instructions: impl Into<String>) -> Self {
         self.body.instructions = Some(instructions.into());
         self
@@ -128,17 +244,263 @@ impl<'a> CreateResponseRequestBuilder<'a> {
         self
     }
 
+    /// Attaches the request to a conversation.
+    ///
+    /// Items from the conversation are prepended to the input, and the input
+    /// and output of this response are added to the conversation afterwards.
+    pub fn conversation(mut self, id: impl Into<String>) -> Self {
+        self.body.conversation = Some(models::ConversationParam::Id(id.into()));
+        self
+    }
+
+    /// Runs the request in the background and returns immediately.
+    ///
+    /// Background responses must be stored, so enabling this also sets
+    /// `store=true`. Use [`Responses::wait_until_terminal`] to poll for the result.
+    pub fn background(mut self, background: bool) -> Self {
+        self.body.background = Some(background);
+        if background {
+            self.body.store = Some(true);
+        }
+        self
+    }
+
+    /// Adds additional output data to include in the response.
+    pub fn include(mut self, include: Vec<models::IncludeEnum>) -> Self {
+        self.body.include = Some(include);
+        self
+    }
+
+    /// Attaches up to 16 key-value pairs to the response.
+    pub fn metadata(mut self, metadata: models::MetadataParam) -> Self {
+        self.body.metadata = Some(metadata);
+        self
+    }
+
+    pub fn presence_penalty(mut self, presence_penalty: f64) -> Self {
+        self.body.presence_penalty = Some(presence_penalty);
+        self
+    }
+
+    pub fn frequency_penalty(mut self, frequency_penalty: f64) -> Self {
+        self.body.frequency_penalty = Some(frequency_penalty);
+        self
+    }
+
+    pub fn parallel_tool_calls(mut self, parallel_tool_calls: bool) -> Self {
+        self.body.parallel_tool_calls = Some(parallel_tool_calls);
+        self
+    }
+
+    /// Sets options for streamed responses. Only applies to [`send_stream`](Self::send_stream).
+    pub fn stream_options(mut self, stream_options: models::StreamOptionsParam) -> Self {
+        self.body.stream_options = Some(stream_options);
+        self
+    }
+
+    pub fn max_tool_calls(mut self, max_tool_calls: i32) -> Self {
+        self.body.max_tool_calls = Some(max_tool_calls);
+        self
+    }
+
+    pub fn safety_identifier(mut self, safety_identifier: impl Into<String>) -> Self {
+        self.body.safety_identifier = Some(safety_identifier.into());
+        self
+    }
+
+    pub fn prompt_cache_key(mut self, prompt_cache_key: impl Into<String>) -> Self {
+        self.body.prompt_cache_key = Some(prompt_cache_key.into());
+        self
+    }
+
+    /// Whether to store the response so it can be retrieved later. Defaults to `false`.
+    pub fn store(mut self, store: bool) -> Self {
+        self.body.store = Some(store);
+        self
+    }
+
+    pub fn top_logprobs(mut self, top_logprobs: i32) -> Self {
+        self.body.top_logprobs = Some(top_logprobs);
+        self
+    }
+
+    /// Adds a field the typed setters don't cover to the request body.
+    ///
+    /// This is meant for vendor-specific parameters. Fields that are also set
+    /// through a typed setter are reported by [`validate`](Self::validate)
+    /// instead of being overwritten.
+    pub fn extra_body(
+        mut self,
+        key: impl Into<String>,
+        value: impl Into<serde_json::Value>,
+    ) -> Self {
+        self.extra_body.insert(key.into(), value.into());
+        self
+    }
+
+    /// Adds a header to this request, overriding client-wide defaults.
+    ///
+    /// `Content-Type` and `Accept` are set by the client and are reported by
+    /// [`validate`](Self::validate).
+    pub fn extra_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.extra_headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Adds a query parameter to the request URL.
+    pub fn extra_query(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.extra_query.push((key.into(), value.into()));
+        self
+    }
+
+    /// Overrides the client-wide [timeout](crate::client::ClientBuilder::timeout)
+    /// for this request.
+    ///
+    /// For [`send_stream`](Self::send_stream) it limits the total duration of
+    /// the stream; use [`idle_timeout`](crate::client::ResponseEventStream::idle_timeout)
+    /// on the stream to limit the time between events instead. Only applies to
+    /// the default transport.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Aborts the request, or the returned event stream, with
+    /// [`Error::Cancelled`] once `token` is cancelled.
+    pub fn cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.cancel = Some(token);
+        self
+    }
+
+    /// Sets the `Idempotency-Key` header instead of generating one.
+    ///
+    /// Every send attempt carries an `Idempotency-Key`, and retries reuse the
+    /// key of the first attempt, so the server creates the response at most
+    /// once. The key is reported in [`ApiError::idempotency_key`] and
+    /// [`RequestInfo::idempotency_key`]. Set it yourself to reconcile requests
+    /// that failed without a response, e.g. after a timeout.
+    ///
+    /// [`ApiError::idempotency_key`]: crate::client::ApiError::idempotency_key
+    /// [`RequestInfo::idempotency_key`]: crate::client::RequestInfo::idempotency_key
+    pub fn idempotency_key(mut self, key: impl Into<String>) -> Self {
+        self.idempotency_key = Some(key.into());
+        self
+    }
+
+    /// Adds a model to replay the request with when the previous model fails
+    /// with a rate limit, an overload or a context-length error, or while its
+    /// [circuit](crate::client::CircuitBreaker) is open.
+    ///
+    /// Models are tried in the order they were added, after the client's
+    /// [`RetryPolicy`](crate::client::RetryPolicy) gave up on the previous one.
+    /// Each model is sent as a separate request with its own idempotency key.
+    /// [`ResponseResource::model`](models::ResponseResource::model) tells which
+    /// model answered.
+    pub fn fallback(mut self, fallback: impl Into<ModelFallback>) -> Self {
+        self.fallbacks.push(fallback.into());
+        self
+    }
+
+    /// Checks the request against the documented parameter constraints.
+    ///
+    /// Called automatically by [`send`](Self::send) and
+    /// [`send_stream`](Self::send_stream). Returns [`Error::Validation`] listing
+    /// every offending field.
+    pub fn validate(&self) -> Result<()> {
+        let mut v = validation::create_response(&self.body);
+        v.extra_body(&self.body, &self.extra_body);
+        v.extra_headers(&self.extra_headers);
+        v.fallbacks(&self.fallbacks);
+        v.finish()
+    }
+
+    /// Builds a `POST` request to `path`, with the idempotency key, extra
+    /// body fields, headers and query parameters merged in.
+    fn request(
+        &self,
+        path: &str,
+        body: &impl serde::Serialize,
+        idempotency_key: Option<String>,
+    ) -> Result<http::RequestBuilder> {
+        let mut url = self.client.endpoint_url(path)?;
+        if !self.extra_query.is_empty() {
+            url.query_pairs_mut().extend_pairs(&self.extra_query);
+        }
+
+        let mut request = self.client.post(url).timeout(self.timeout);
+        if let Some(idempotency_key) = idempotency_key {
+            request = request.header(http::IDEMPOTENCY_KEY, idempotency_key);
+        }
+        for (name, value) in &self.extra_headers {
+            let name = reqwest::header::HeaderName::try_from(name.as_str())
+                .map_err(|e| Error::InvalidHeaderValue(e.to_string()))?;
+            request = request.header(name, value);
+        }
+
+        if self.extra_body.is_empty() {
+            return Ok(request.json(body));
+        }
+        let mut body = serde_json::to_value(body)?;
+        if let serde_json::Value::Object(fields) = &mut body {
+            fields.extend(self.extra_body.clone());
+        }
+        Ok(request.json(&body))
+    }
+
+    /// Sends the request, moving on to the next fallback model while the
+    /// previous one fails with a capacity or context-length error.
+    ///
+    /// Returns the first successful response, or the error of the last model.
+    async fn execute(&self, stream: bool) -> Result<http::Response> {
+        let mut attempt = 0;
+        loop {
+            let mut body = self.body.clone();
+            let mut idempotency_key = self.idempotency_key.clone();
+            if attempt > 0 {
+                self.fallbacks[attempt - 1].apply(&mut body);
+                idempotency_key = idempotency_key.map(|key| format!("{key}-fallback-{attempt}"));
+            }
+            let idempotency_key = idempotency_key.unwrap_or_else(http::idempotency_key);
+
+            let mut request = self.request("responses", &body, Some(idempotency_key))?;
+            if stream {
+                request = request.header(reqwest::header::ACCEPT, "text/event-stream");
+            }
+            let err = match self.client.execute(request).await {
+                Ok(resp) if resp.status().is_success() => return Ok(resp),
+                Ok(resp) => http::read_error_body(resp).await?,
+                Err(err @ Error::CircuitOpen { .. }) => err,
+                Err(err) => return Err(err),
+            };
+            let retry_next = matches!(err, Error::CircuitOpen { .. })
+                || err.api_error().is_some_and(|e| {
+                    e.is_rate_limited() || e.is_overloaded() || e.is_context_length_exceeded()
+                });
+            if !retry_next || attempt == self.fallbacks.len() {
+                return Err(err);
+            }
+            attempt += 1;
+        }
+    }
+
     /// Sends the request and returns the full response resource.
     pub async fn send(self) -> Result<models::ResponseResource> {
-        let url = self.client.endpoint_url("responses")?;
+        Ok(self.send_with_meta().await?.data)
+    }
 
-        let resp = self.client.http().post(url).json(&self.body).send().await?;
+    /// Like [`send`](Self::send), but also returns the HTTP response metadata,
+    /// such as the request ID and rate-limit headers.
+    pub async fn send_with_meta(self) -> Result<WithMeta<models::ResponseResource>> {
+        self.validate()?;
 
-        if !resp.status().is_success() {
-            return Err(http::read_error_body(resp).await?);
-        }
+        cancel::or_cancel(self.cancel.as_ref(), async {
+            let resp = self.execute(false).await?;
 
-        Ok(resp.json::<models::ResponseResource>().await?)
+            let meta = ResponseMeta::from_response(&resp);
+            let data = resp.json::<models::ResponseResource>().await?;
+            Ok(WithMeta { data, meta })
+        })
+        .await
     }
 
     /// Sends the request with streaming enabled and returns a stream of events.
@@ -146,24 +508,251 @@ impl<'a> CreateResponseRequestBuilder<'a> {
     /// This force-sets `stream=true` on the request body. The returned
     /// [`ResponseEventStream`](crate::client::ResponseEventStream) yields
     /// `Result<StreamingEvent>` items decoded from the SSE response.
-    pub async fn send_stream(mut self) -> Result<sse::ResponseEventStream> {
+    pub async fn send_stream(self) -> Result<sse::ResponseEventStream> {
+        Ok(self.send_stream_with_meta().await?.data)
+    }
+
+    /// Like [`send_stream`](Self::send_stream), but also returns the metadata
+    /// of the HTTP response that carries the event stream.
+    pub async fn send_stream_with_meta(mut self) -> Result<WithMeta<sse::ResponseEventStream>> {
+        self.validate()?;
         self.body.stream = Some(true);
 
-        let url = self.client.endpoint_url("responses")?;
+        let resp = cancel::or_cancel(self.cancel.as_ref(), self.execute(true)).await?;
 
-        let resp = self
+        let meta = ResponseMeta::from_response(&resp);
+        let mut data = self.client.event_stream(resp)?;
+        if let Some(token) = self.cancel {
+            data = data.cancel_on(token);
+        }
+        Ok(WithMeta { data, meta })
+    }
+}
+
+/// A model to fall back to, with optional overrides of the request.
+///
+/// Strings convert into a fallback without overrides. See
+/// [`CreateResponseRequestBuilder::fallback`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct ModelFallback {
+    pub(crate) model: String,
+    reasoning: Option<models::ReasoningParam>,
+    pub(crate) max_output_tokens: Option<i32>,
+}
+
+impl ModelFallback {
+    /// Falls back to `model` with the request unchanged otherwise.
+    pub fn new(model: impl Into<String>) -> Self {
+        Self {
+            model: model.into(),
+            reasoning: None,
+            max_output_tokens: None,
+        }
+    }
+
+    /// Replaces the reasoning configuration for this model.
+    pub fn reasoning(mut self, reasoning: models::ReasoningParam) -> Self {
+        self.reasoning = Some(reasoning);
+        self
+    }
+
+    /// Replaces the upper bound for the number of output tokens for this model.
+    pub fn max_output_tokens(mut self, max_output_tokens: i32) -> Self {
+        self.max_output_tokens = Some(max_output_tokens);
+        self
+    }
+
+    fn apply(&self, body: &mut models::CreateResponseBody) {
+        body.model = Some(self.model.clone());
+        if let Some(reasoning) = &self.reasoning {
+            body.reasoning = Some(reasoning.clone());
+        }
+        if let Some(max_output_tokens) = self.max_output_tokens {
+            body.max_output_tokens = Some(max_output_tokens);
+        }
+    }
+}
+
+impl From<&str> for ModelFallback {
+    fn from(model: &str) -> Self {
+        Self::new(model)
+    }
+}
+
+impl From<String> for ModelFallback {
+    fn from(model: String) -> Self {
+        Self::new(model)
+    }
+}
+
+/// Request builder for `GET /responses/{id}`.
+#[derive(Debug)]
+pub struct RetrieveResponseRequestBuilder<'a> {
+    client: &'a Client,
+    id: String,
+    include: Vec<models::IncludeEnum>,
+    starting_after: Option<i32>,
+}
+
+impl<'a> RetrieveResponseRequestBuilder<'a> {
+    /// Adds additional output data to include in the retrieved response.
+    pub fn include(mut self, include: Vec<models::IncludeEnum>) -> Self {
+        self.include = include;
+        self
+    }
+
+    /// When streaming, only replays events with a sequence number after this one.
+    pub fn starting_after(mut self, sequence_number: i32) -> Self {
+        self.starting_after = Some(sequence_number);
+        self
+    }
+
+    /// Sends the request and returns the stored response resource.
+    pub async fn send(self) -> Result<models::ResponseResource> {
+        let url = self.url(false)?;
+
+        let resp = self.client.execute(self.client.get(url)).await?;
+
+        http::resource_json(resp).await
+    }
+
+    /// Sends the request with `stream=true` and replays the stored events.
+    ///
+    /// The returned [`ResponseEventStream`](crate::client::ResponseEventStream)
+    /// yields the same events the response emitted when it was generated.
+    pub async fn send_stream(self) -> Result<sse::ResponseEventStream> {
+        let url = self.url(true)?;
+
+        let request = self
             .client
-            .http()
-            .post(url)
-            .header(reqwest::header::ACCEPT, "text/event-stream")
-            .json(&self.body)
-            .send()
-            .await?;
+            .get(url)
+            .header(reqwest::header::ACCEPT, "text/event-stream");
+
+        let resp = self.client.execute(request).await?;
 
         if !resp.status().is_success() {
-            return Err(http::read_error_body(resp).await?);
+            return Err(http::read_resource_error_body(resp).await?);
         }
 
-        sse::ResponseEventStream::from_response(resp)
+        self.client.event_stream(resp)
+    }
+
+    fn url(&self, stream: bool) -> Result<url::Url> {
+        let mut url = self.client.resource_url(&["responses", &self.id])?;
+        {
+            let mut query = url.query_pairs_mut();
+            for include in &self.include {
+                query.append_pair("include[]", include_value(include));
+            }
+            if stream {
+                query.append_pair("stream", "true");
+                if let Some(seq) = self.starting_after {
+                    query.append_pair("starting_after", &seq.to_string());
+                }
+            }
+        }
+        // An empty query would otherwise serialize as a trailing `?`.
+        if url.query() == Some("") {
+            url.set_query(None);
+        }
+        Ok(url)
+    }
+}
+
+impl ListRequestBuilder<'_, models::ItemField> {
+    /// Adds additional data to include in the listed items.
+    pub fn include(self, include: Vec<models::IncludeEnum>) -> Self {
+        include.iter().fold(self, |builder, include| {
+            builder.query_pair("include[]", include_value(include))
+        })
+    }
+}
+
+/// Returns the wire value of an [`IncludeEnum`](models::IncludeEnum).
+fn include_value(include: &models::IncludeEnum) -> &'static str {
+    match include {
+        models::IncludeEnum::ReasoningEncryptedContent => "reasoning.encrypted_content",
+        models::IncludeEnum::MessageOutputTextLogprobs => "message.output_text.logprobs",
+    }
+}
+
+/// Polling helper returned by [`Responses::wait_until_terminal`].
+///
+/// The delay between polls starts at [`poll_interval`](Self::poll_interval) and
+/// is multiplied by [`backoff_multiplier`](Self::backoff_multiplier) after every
+/// poll, capped at [`max_poll_interval`](Self::max_poll_interval).
+#[derive(Debug)]
+pub struct WaitUntilTerminalBuilder<'a> {
+    client: &'a Client,
+    id: String,
+    poll_interval: Duration,
+    max_poll_interval: Duration,
+    backoff_multiplier: f64,
+    timeout: Option<Duration>,
+}
+
+impl<'a> WaitUntilTerminalBuilder<'a> {
+    /// Sets the delay before the second poll. Defaults to 500ms.
+    pub fn poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    /// Sets the upper bound for the delay between polls. Defaults to 10s.
+    pub fn max_poll_interval(mut self, interval: Duration) -> Self {
+        self.max_poll_interval = interval;
+        self
+    }
+
+    /// Sets the factor the delay grows by after each poll. Defaults to `2.0`.
+    ///
+    /// Factors below `1.0` and non-finite factors are treated as `1.0`.
+    pub fn backoff_multiplier(mut self, multiplier: f64) -> Self {
+        self.backoff_multiplier = if multiplier.is_finite() {
+            multiplier.max(1.0)
+        } else {
+            1.0
+        };
+        self
+    }
+
+    /// Gives up with [`Error::PollTimeout`] once this much time has passed.
+    ///
+    /// Without a timeout, polling continues until a terminal status is reached.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Polls until the response reaches a terminal status and returns it.
+    pub async fn send(self) -> Result<models::ResponseResource> {
+        let started = Instant::now();
+        let mut delay = self.poll_interval;
+
+        loop {
+            let resp = Responses::new(self.client)
+                .retrieve(&self.id)
+                .send()
+                .await?;
+            if TERMINAL_STATUSES.contains(&resp.status.as_str()) {
+                return Ok(resp);
+            }
+
+            if let Some(timeout) = self.timeout {
+                let remaining = timeout.saturating_sub(started.elapsed());
+                if remaining.is_zero() {
+                    return Err(Error::PollTimeout {
+                        id: self.id,
+                        status: resp.status,
+                    });
+                }
+                delay = delay.min(remaining);
+            }
+
+            tokio::time::sleep(delay).await;
+            delay = Duration::try_from_secs_f64(delay.as_secs_f64() * self.backoff_multiplier)
+                .unwrap_or(self.max_poll_interval)
+                .min(self.max_poll_interval);
+        }
     }
 }
@@ -12,14 +12,232 @@ pub enum Error {
     #[error("invalid header value: {0}")]
     InvalidHeaderValue(String),
 
-    #[error("http error status {status}: {body}")]
-    HttpStatus {
-        status: reqwest::StatusCode,
-        body: String,
-    },
+    #[error("configuration error: {0}")]
+    Config(String),
+
+    #[error("transport error: {0}")]
+    Transport(#[from] TransportError),
+
+    #[error("json error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("interceptor error: {0}")]
+    Interceptor(Box<dyn std::error::Error + Send + Sync>),
+
+    #[error("credentials error: {0}")]
+    Credentials(Box<dyn std::error::Error + Send + Sync>),
+
+    #[error("api error: {0}")]
+    Api(Box<ApiError>),
+
+    #[error("request validation failed: {}", display_field_errors(.errors))]
+    Validation { errors: Vec<FieldError> },
+
+    #[error("resource not found: {0}")]
+    NotFound(Box<ApiError>),
+
+    #[error("response {id} did not reach a terminal status in time (last status: {status})")]
+    PollTimeout { id: String, status: String },
 
     #[error("streaming error: {0}")]
     Streaming(#[from] StreamingError),
+
+    #[error("request was cancelled")]
+    Cancelled,
+
+    #[error("circuit breaker is open for {base_url}{}", display_model(.model))]
+    CircuitOpen {
+        base_url: String,
+        model: Option<String>,
+    },
+}
+
+impl Error {
+    /// Returns the API error if this error was caused by an unsuccessful response.
+    pub fn api_error(&self) -> Option<&ApiError> {
+        match self {
+            Error::Api(err) | Error::NotFound(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// An unsuccessful HTTP response, with the OpenResponses error envelope parsed
+/// where possible.
+///
+/// The envelope has the shape `{"error": {"type", "code", "message", "param"}}`.
+/// Servers that return something else still produce an `ApiError`; only the
+/// [`status`](Self::status) and the raw [`body`](Self::body) are set then.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiError {
+    /// The HTTP status code of the response.
+    pub status: reqwest::StatusCode,
+    /// The value of the `x-request-id` response header, if present.
+    pub request_id: Option<String>,
+    /// The `Idempotency-Key` the request was sent with, if any.
+    pub idempotency_key: Option<String>,
+    /// The error type, e.g. `invalid_request_error`.
+    pub error_type: Option<String>,
+    /// A machine-readable error code, e.g. `context_length_exceeded`.
+    pub code: Option<String>,
+    /// A human-readable description of the error.
+    pub message: Option<String>,
+    /// The request parameter the error relates to, if any.
+    pub param: Option<String>,
+    /// The raw response body, cut off after a size limit.
+    pub body: String,
+    /// Whether [`body`](Self::body) was cut off.
+    pub body_truncated: bool,
+}
+
+impl ApiError {
+    /// Whether the request was rejected because of a rate limit.
+    ///
+    /// Exhausted quotas are reported with the same status code, but are not
+    /// considered rate limits since retrying won't help.
+    pub fn is_rate_limited(&self) -> bool {
+        self.status == reqwest::StatusCode::TOO_MANY_REQUESTS && !self.is_quota_exceeded()
+    }
+
+    /// Whether the account has exhausted its quota.
+    pub fn is_quota_exceeded(&self) -> bool {
+        self.code.as_deref() == Some("insufficient_quota")
+            || self.error_type.as_deref() == Some("insufficient_quota")
+    }
+
+    /// Whether the input exceeded the model's context window.
+    pub fn is_context_length_exceeded(&self) -> bool {
+        self.code.as_deref() == Some("context_length_exceeded")
+            || self
+                .message
+                .as_deref()
+                .is_some_and(|m| m.contains("maximum context length"))
+    }
+
+    /// Whether the server is temporarily unable to handle the request.
+    pub fn is_overloaded(&self) -> bool {
+        matches!(self.status.as_u16(), 503 | 529)
+            || self.error_type.as_deref() == Some("overloaded_error")
+    }
+
+    /// Whether the request was rejected due to missing or invalid credentials
+    /// or insufficient permissions.
+    pub fn is_auth(&self) -> bool {
+        matches!(
+            self.status,
+            reqwest::StatusCode::UNAUTHORIZED | reqwest::StatusCode::FORBIDDEN
+        ) || matches!(
+            self.error_type.as_deref(),
+            Some("authentication_error" | "permission_error")
+        )
+    }
+
+    /// Whether retrying the same request may succeed.
+    pub fn is_retryable(&self) -> bool {
+        match self.status.as_u16() {
+            408 | 409 => true,
+            429 => self.is_rate_limited(),
+            status => status >= 500,
+        }
+    }
+}
+
+impl std::fmt::Display for ApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "http error status {}", self.status)?;
+        match (&self.message, &self.code) {
+            (Some(message), Some(code)) => write!(f, ": {message} ({code})")?,
+            (Some(message), None) => write!(f, ": {message}")?,
+            (None, _) if !self.body.is_empty() => write!(f, ": {}", self.body)?,
+            (None, _) => {}
+        }
+        if let Some(request_id) = &self.request_id {
+            write!(f, " [request id: {request_id}]")?;
+        }
+        if let Some(idempotency_key) = &self.idempotency_key {
+            write!(f, " [idempotency key: {idempotency_key}]")?;
+        }
+        Ok(())
+    }
+}
+
+/// A failure of a [`Transport`](crate::client::Transport), carried by
+/// [`Error::Transport`].
+///
+/// Failures that may go away on another attempt, such as a refused
+/// connection or a timeout, are created with [`retryable`](Self::retryable)
+/// and retried according to the client's
+/// [`RetryPolicy`](crate::client::RetryPolicy).
+#[derive(Debug)]
+pub struct TransportError {
+    source: Box<dyn std::error::Error + Send + Sync>,
+    retryable: bool,
+}
+
+impl TransportError {
+    /// A failure that retrying won't fix.
+    pub fn new(source: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> Self {
+        Self {
+            source: source.into(),
+            retryable: false,
+        }
+    }
+
+    /// A failure that may succeed on another attempt, such as a refused
+    /// connection or a timeout.
+    pub fn retryable(source: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> Self {
+        Self {
+            source: source.into(),
+            retryable: true,
+        }
+    }
+
+    /// Whether retrying the request may succeed.
+    pub fn is_retryable(&self) -> bool {
+        self.retryable
+    }
+}
+
+impl std::fmt::Display for TransportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.source.fmt(f)
+    }
+}
+
+impl std::error::Error for TransportError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&*self.source)
+    }
+}
+
+/// A client-side validation failure for a single request field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldError {
+    /// The name of the offending field, e.g. `temperature` or `metadata.user`.
+    pub field: String,
+    /// A human-readable description of the violated constraint.
+    pub message: String,
+}
+
+impl std::fmt::Display for FieldError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
+}
+
+fn display_field_errors(errors: &[FieldError]) -> String {
+    errors
+        .iter()
+        .map(FieldError::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+fn display_model(model: &Option<String>) -> String {
+    match model {
+        Some(model) => format!(" (model {model})"),
+        None => String::new(),
+    }
 }
 
 /// Errors specific to SSE streaming.
@@ -42,4 +260,7 @@ pub enum StreamingError {
 
     #[error("invalid UTF-8 in SSE stream: {0}")]
     Utf8(#[from] std::string::FromUtf8Error),
+
+    #[error("no data received for {timeout:?}")]
+    IdleTimeout { timeout: std::time::Duration },
 }
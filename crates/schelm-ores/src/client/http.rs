@@ -1,4 +1,8 @@
-use crate::client::{Error, Result};
+use crate::client::transport::Body;
+use crate::client::{ApiError, Error, Result, TransportError};
+
+use bytes::Bytes;
+use reqwest::header::{CONTENT_TYPE, HeaderMap, HeaderName, HeaderValue};
 
 /// Normalizes a base URL so that URL joining behaves like appending path segments.
 ///
@@ -21,16 +25,452 @@ pub(crate) fn join(base_url: &url::Url, path: &str) -> Result<url::Url> {
     Ok(base.join(path)?)
 }
 
-pub(crate) async fn read_error_body(resp: reqwest::Response) -> Result<Error> {
+/// Joins path segments onto a normalized base URL, percent-encoding each segment.
+///
+/// Use this instead of [`join`] when a segment holds a caller-supplied value such as
+/// a resource ID, so that characters like `/` or `?` can't alter the path.
+pub(crate) fn join_segments(base_url: &url::Url, segments: &[&str]) -> Result<url::Url> {
+    let mut url = normalize_base_url(base_url.clone());
+    url.path_segments_mut()
+        .map_err(|_| url::ParseError::RelativeUrlWithCannotBeABaseBase)?
+        .pop_if_empty()
+        .extend(segments);
+    Ok(url)
+}
+
+/// The header that lets the server deduplicate retried requests.
+pub(crate) const IDEMPOTENCY_KEY: HeaderName = HeaderName::from_static("idempotency-key");
+
+/// Generates a random idempotency key in the UUID v4 format.
+pub(crate) fn idempotency_key() -> String {
+    uuid::Uuid::new_v4().to_string()
+}
+
+/// Returns the `Idempotency-Key` header, if set to a valid string.
+pub(crate) fn idempotency_key_of(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get(IDEMPOTENCY_KEY)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_owned)
+}
+
+/// The idempotency key an attempt was sent with, attached to its response.
+#[derive(Clone, Debug)]
+pub(crate) struct SentIdempotencyKey(pub(crate) String);
+
+/// A per-request timeout, passed to the transport as a request extension.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct RequestTimeout(pub(crate) std::time::Duration);
+
+/// A request that is ready to be handed to the transport.
+///
+/// The body is buffered, so requests can be cloned for retries.
+#[derive(Clone, Debug)]
+pub(crate) struct Request {
+    pub(crate) method: ::http::Method,
+    pub(crate) url: url::Url,
+    pub(crate) headers: HeaderMap,
+    pub(crate) body: Option<Bytes>,
+    pub(crate) timeout: Option<std::time::Duration>,
+}
+
+impl Request {
+    pub(crate) fn into_http(self) -> Result<::http::Request<Bytes>> {
+        let mut request = ::http::Request::builder()
+            .method(self.method)
+            .uri(self.url.as_str())
+            .body(self.body.unwrap_or_default())
+            .map_err(TransportError::new)?;
+        *request.headers_mut() = self.headers;
+        if let Some(timeout) = self.timeout {
+            request.extensions_mut().insert(RequestTimeout(timeout));
+        }
+        Ok(request)
+    }
+}
+
+/// Builds a [`Request`]; errors are deferred until [`build`](Self::build).
+#[derive(Debug)]
+pub(crate) struct RequestBuilder {
+    request: Result<Request>,
+}
+
+impl RequestBuilder {
+    pub(crate) fn new(method: ::http::Method, url: url::Url) -> Self {
+        Self {
+            request: Ok(Request {
+                method,
+                url,
+                headers: HeaderMap::new(),
+                body: None,
+                timeout: None,
+            }),
+        }
+    }
+
+    /// Sets a header, replacing any previous value.
+    pub(crate) fn header(mut self, name: HeaderName, value: impl AsRef<str>) -> Self {
+        if let Ok(request) = &mut self.request {
+            match HeaderValue::from_str(value.as_ref()) {
+                Ok(value) => {
+                    request.headers.insert(name, value);
+                }
+                Err(e) => self.request = Err(Error::InvalidHeaderValue(e.to_string())),
+            }
+        }
+        self
+    }
+
+    /// Serializes `body` as the JSON request body.
+    pub(crate) fn json<T: serde::Serialize + ?Sized>(mut self, body: &T) -> Self {
+        if let Ok(request) = &mut self.request {
+            match serde_json::to_vec(body) {
+                Ok(bytes) => {
+                    request.body = Some(bytes.into());
+                    request
+                        .headers
+                        .entry(CONTENT_TYPE)
+                        .or_insert(HeaderValue::from_static("application/json"));
+                }
+                Err(e) => self.request = Err(e.into()),
+            }
+        }
+        self
+    }
+
+    /// Sets a raw request body.
+    pub(crate) fn body(mut self, body: impl Into<Bytes>) -> Self {
+        if let Ok(request) = &mut self.request {
+            request.body = Some(body.into());
+        }
+        self
+    }
+
+    /// Overrides the client-wide timeout for this request.
+    pub(crate) fn timeout(mut self, timeout: Option<std::time::Duration>) -> Self {
+        if let Ok(request) = &mut self.request {
+            request.timeout = timeout;
+        }
+        self
+    }
+
+    pub(crate) fn build(self) -> Result<Request> {
+        self.request
+    }
+}
+
+/// A response whose headers have arrived and whose body can be read.
+#[derive(Debug)]
+pub(crate) struct Response {
+    status: reqwest::StatusCode,
+    headers: HeaderMap,
+    extensions: ::http::Extensions,
+    body: Body,
+}
+
+impl Response {
+    pub(crate) fn from_http(resp: ::http::Response<Body>) -> Self {
+        let (parts, body) = resp.into_parts();
+        Self {
+            status: parts.status,
+            headers: parts.headers,
+            extensions: parts.extensions,
+            body,
+        }
+    }
+
+    pub(crate) fn status(&self) -> reqwest::StatusCode {
+        self.status
+    }
+
+    pub(crate) fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    pub(crate) fn extensions(&self) -> &::http::Extensions {
+        &self.extensions
+    }
+
+    pub(crate) fn extensions_mut(&mut self) -> &mut ::http::Extensions {
+        &mut self.extensions
+    }
+
+    /// Reads the whole body.
+    pub(crate) async fn bytes(mut self) -> Result<Bytes> {
+        let mut buf = Vec::new();
+        while let Some(chunk) = self.body.chunk().await? {
+            buf.extend_from_slice(&chunk);
+        }
+        Ok(buf.into())
+    }
+
+    /// Reads the whole body and decodes it as JSON.
+    pub(crate) async fn json<T: serde::de::DeserializeOwned>(self) -> Result<T> {
+        let bytes = self.bytes().await?;
+        Ok(serde_json::from_slice(&bytes)?)
+    }
+
+    pub(crate) fn into_body(self) -> Body {
+        self.body
+    }
+}
+
+/// A `multipart/form-data` body that is encoded in memory.
+///
+/// Unlike a streamed form, the encoded body can be cloned, so requests
+/// carrying it behave like any other request with a buffered body.
+pub(crate) struct Multipart {
+    boundary: String,
+    body: Vec<u8>,
+}
+
+impl Multipart {
+    pub(crate) fn new() -> Self {
+        use std::hash::{BuildHasher, RandomState};
+
+        let nonce = RandomState::new().hash_one(std::time::SystemTime::now());
+        Self {
+            boundary: format!("schelm-ores-{nonce:016x}"),
+            body: Vec::new(),
+        }
+    }
+
+    /// Appends a plain text field.
+    pub(crate) fn text(mut self, name: &str, value: &str) -> Self {
+        self.part_header(name, None, None);
+        self.body.extend_from_slice(value.as_bytes());
+        self.body.extend_from_slice(b"\r\n");
+        self
+    }
+
+    /// Appends a file field.
+    ///
+    /// Fails if `content_type` is not a valid header value, so it can't
+    /// inject further part headers.
+    pub(crate) fn file(
+        mut self,
+        name: &str,
+        filename: &str,
+        content_type: &str,
+        data: &[u8],
+    ) -> Result<Self> {
+        HeaderValue::from_str(content_type)
+            .map_err(|e| Error::InvalidHeaderValue(e.to_string()))?;
+        self.part_header(name, Some(filename), Some(content_type));
+        self.body.extend_from_slice(data);
+        self.body.extend_from_slice(b"\r\n");
+        Ok(self)
+    }
+
+    /// Returns the `Content-Type` header value, including the boundary.
+    pub(crate) fn content_type(&self) -> String {
+        format!("multipart/form-data; boundary={}", self.boundary)
+    }
+
+    /// Terminates the form and returns the encoded body.
+    pub(crate) fn finish(mut self) -> Vec<u8> {
+        self.body
+            .extend_from_slice(format!("--{}--\r\n", self.boundary).as_bytes());
+        self.body
+    }
+
+    fn part_header(&mut self, name: &str, filename: Option<&str>, content_type: Option<&str>) {
+        let mut header = format!(
+            "--{}\r\nContent-Disposition: form-data; name=\"{}\"",
+            self.boundary,
+            escape_quoted(name)
+        );
+        if let Some(filename) = filename {
+            header.push_str(&format!("; filename=\"{}\"", escape_quoted(filename)));
+        }
+        header.push_str("\r\n");
+        if let Some(content_type) = content_type {
+            header.push_str(&format!("Content-Type: {content_type}\r\n"));
+        }
+        header.push_str("\r\n");
+        self.body.extend_from_slice(header.as_bytes());
+    }
+}
+
+/// Escapes a quoted `Content-Disposition` parameter the way browsers do.
+fn escape_quoted(value: &str) -> String {
+    value
+        .replace('"', "%22")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Whether `resp` is a `429` caused by an exhausted quota, which retrying
+/// can't fix.
+///
+/// Reads at most [`MAX_ERROR_BODY_BYTES`] and puts them back, so the response
+/// can still be read in full. A body that fails to read is not a quota error.
+pub(crate) async fn is_quota_exceeded(resp: &mut Response) -> bool {
+    if resp.status() != reqwest::StatusCode::TOO_MANY_REQUESTS {
+        return false;
+    }
+    let mut chunks = Vec::new();
+    let (raw, body_truncated) = read_capped(&mut resp.body, |chunk| chunks.push(chunk)).await;
+    let rest = std::mem::replace(&mut resp.body, Body::empty());
+    resp.body = rest.prepend(chunks);
+    parse_api_error(resp.status(), None, raw, body_truncated).is_quota_exceeded()
+}
+
+/// Whether an attempt failed because of the server rather than the request:
+/// it could not connect, timed out, hit a retryable transport failure or got
+/// a `5xx` response.
+pub(crate) fn is_server_failure(result: &Result<Response>) -> bool {
+    match result {
+        Ok(resp) => resp.status().is_server_error(),
+        Err(Error::Reqwest(e)) => e.is_connect() || e.is_timeout(),
+        Err(Error::Transport(e)) => e.is_retryable(),
+        Err(_) => false,
+    }
+}
+
+/// Decodes the JSON body of a successful response, or reads the error body.
+pub(crate) async fn json<T: serde::de::DeserializeOwned>(resp: Response) -> Result<T> {
+    if !resp.status().is_success() {
+        return Err(read_error_body(resp).await?);
+    }
+    resp.json::<T>().await
+}
+
+/// Like [`json`], but maps `404 Not Found` to [`Error::NotFound`].
+pub(crate) async fn resource_json<T: serde::de::DeserializeOwned>(resp: Response) -> Result<T> {
+    if !resp.status().is_success() {
+        return Err(read_resource_error_body(resp).await?);
+    }
+    resp.json::<T>().await
+}
+
+/// Maximum number of error body bytes that are read and kept (64 KiB).
+const MAX_ERROR_BODY_BYTES: usize = 64 * 1024;
+
+/// The OpenResponses error envelope.
+#[derive(serde::Deserialize)]
+struct ErrorEnvelope {
+    error: ErrorObject,
+}
+
+#[derive(serde::Deserialize)]
+struct ErrorObject {
+    #[serde(rename = "type")]
+    ty: Option<String>,
+    code: Option<serde_json::Value>,
+    message: Option<String>,
+    param: Option<serde_json::Value>,
+}
+
+/// Turns an unsuccessful response into an [`Error::Api`].
+///
+/// Reads at most [`MAX_ERROR_BODY_BYTES`] of the body. Errors while reading the
+/// body are ignored, since the status code is what matters most.
+pub(crate) async fn read_error_body(mut resp: Response) -> Result<Error> {
     let status = resp.status();
-    let body = resp.text().await.unwrap_or_default();
-    Ok(Error::HttpStatus { status, body })
+    let request_id = resp
+        .headers()
+        .get("x-request-id")
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_owned);
+    let idempotency_key = resp
+        .extensions()
+        .get::<SentIdempotencyKey>()
+        .map(|key| key.0.clone());
+
+    let (raw, body_truncated) = read_capped(&mut resp.body, drop).await;
+    let mut err = parse_api_error(status, request_id, raw, body_truncated);
+    err.idempotency_key = idempotency_key;
+    Ok(Error::Api(Box::new(err)))
+}
+
+/// Reads at most [`MAX_ERROR_BODY_BYTES`] of an error body, stopping at the
+/// first read error, and hands every chunk read to `keep`.
+///
+/// Returns the bytes and whether the body was cut off.
+async fn read_capped(body: &mut Body, mut keep: impl FnMut(Bytes)) -> (Vec<u8>, bool) {
+    let mut raw = Vec::new();
+    while let Ok(Some(chunk)) = body.chunk().await {
+        let remaining = MAX_ERROR_BODY_BYTES - raw.len();
+        let truncated = chunk.len() > remaining;
+        raw.extend_from_slice(&chunk[..chunk.len().min(remaining)]);
+        keep(chunk);
+        if truncated {
+            return (raw, true);
+        }
+    }
+    (raw, false)
+}
+
+fn parse_api_error(
+    status: reqwest::StatusCode,
+    request_id: Option<String>,
+    raw: Vec<u8>,
+    body_truncated: bool,
+) -> ApiError {
+    let envelope = serde_json::from_slice::<ErrorEnvelope>(&raw).ok();
+    let body = match String::from_utf8(raw) {
+        Ok(body) => body,
+        // Truncation may have split a multi-byte character.
+        Err(e) => String::from_utf8_lossy(e.as_bytes()).into_owned(),
+    };
+    let (error_type, code, message, param) = match envelope {
+        Some(ErrorEnvelope { error }) => (
+            error.ty,
+            error.code.and_then(value_to_string),
+            error.message,
+            error.param.and_then(value_to_string),
+        ),
+        None => (None, None, None, None),
+    };
+
+    ApiError {
+        status,
+        request_id,
+        idempotency_key: None,
+        error_type,
+        code,
+        message,
+        param,
+        body,
+        body_truncated,
+    }
+}
+
+/// Servers disagree on whether codes are strings or numbers.
+fn value_to_string(value: serde_json::Value) -> Option<String> {
+    match value {
+        serde_json::Value::Null => None,
+        serde_json::Value::String(s) => Some(s),
+        other => Some(other.to_string()),
+    }
+}
+
+/// Like [`read_error_body`], but maps `404 Not Found` to [`Error::NotFound`].
+///
+/// Used by endpoints that address a single resource by ID.
+pub(crate) async fn read_resource_error_body(resp: Response) -> Result<Error> {
+    match read_error_body(resp).await? {
+        Error::Api(err) if err.status == reqwest::StatusCode::NOT_FOUND => Ok(Error::NotFound(err)),
+        other => Ok(other),
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn idempotency_keys_are_random_v4_uuids() {
+        let key = idempotency_key();
+        assert_eq!(key.len(), 36);
+        let groups: Vec<_> = key.split('-').map(str::len).collect();
+        assert_eq!(groups, [8, 4, 4, 4, 12]);
+        assert_eq!(&key[14..15], "4");
+        assert!(matches!(&key[19..20], "8" | "9" | "a" | "b"), "{key}");
+        assert_ne!(key, idempotency_key());
+    }
+
     #[test]
     fn normalize_base_url_adds_trailing_slash() {
         let url = url::Url::parse("https://example.com/v1").unwrap();
@@ -58,4 +498,165 @@ mod tests {
         let joined = join(&base, "/responses").unwrap();
         assert_eq!(joined.as_str(), "https://example.com/v1/responses");
     }
+
+    #[test]
+    fn multipart_encodes_text_and_file_parts() {
+        let form = Multipart::new()
+            .text("purpose", "user_data")
+            .file("file", "a\"b.txt", "text/plain", b"hello")
+            .unwrap();
+        let boundary = form.boundary.clone();
+        assert_eq!(
+            form.content_type(),
+            format!("multipart/form-data; boundary={boundary}")
+        );
+
+        let body = String::from_utf8(form.finish()).unwrap();
+        assert_eq!(
+            body,
+            format!(
+                "--{boundary}\r\nContent-Disposition: form-data; name=\"purpose\"\r\n\r\nuser_data\r\n\
+                 --{boundary}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"a%22b.txt\"\r\n\
+                 Content-Type: text/plain\r\n\r\nhello\r\n\
+                 --{boundary}--\r\n"
+            )
+        );
+    }
+
+    #[test]
+    fn multipart_rejects_content_type_with_line_breaks() {
+        let err = Multipart::new()
+            .file("file", "a.txt", "text/plain\r\nX-Injected: yes", b"hello")
+            .err()
+            .expect("content type should be rejected");
+        assert!(matches!(err, Error::InvalidHeaderValue(_)), "got: {err:?}");
+    }
+
+    #[test]
+    fn parse_api_error_reads_envelope() {
+        let raw = serde_json::to_vec(&serde_json::json!({
+            "error": {
+                "type": "invalid_request_error",
+                "code": "context_length_exceeded",
+                "message": "This model's maximum context length is 8192 tokens.",
+                "param": "input"
+            }
+        }))
+        .unwrap();
+        let err = parse_api_error(
+            reqwest::StatusCode::BAD_REQUEST,
+            Some("req_123".into()),
+            raw,
+            false,
+        );
+        assert_eq!(err.error_type.as_deref(), Some("invalid_request_error"));
+        assert_eq!(err.code.as_deref(), Some("context_length_exceeded"));
+        assert_eq!(err.param.as_deref(), Some("input"));
+        assert_eq!(err.request_id.as_deref(), Some("req_123"));
+        assert!(err.is_context_length_exceeded());
+        assert!(!err.is_retryable());
+    }
+
+    #[test]
+    fn parse_api_error_keeps_non_json_body() {
+        let err = parse_api_error(
+            reqwest::StatusCode::BAD_GATEWAY,
+            None,
+            b"upstream unavailable".to_vec(),
+            false,
+        );
+        assert_eq!(err.body, "upstream unavailable");
+        assert_eq!(err.message, None);
+        assert!(err.is_retryable());
+        assert_eq!(
+            err.to_string(),
+            "http error status 502 Bad Gateway: upstream unavailable"
+        );
+    }
+
+    #[test]
+    fn parse_api_error_stringifies_numeric_codes() {
+        let raw = br#"{"error":{"message":"nope","code":42,"param":null}}"#.to_vec();
+        let err = parse_api_error(reqwest::StatusCode::FORBIDDEN, None, raw, false);
+        assert_eq!(err.code.as_deref(), Some("42"));
+        assert_eq!(err.param, None);
+        assert!(err.is_auth());
+    }
+
+    #[test]
+    fn quota_errors_are_not_rate_limits() {
+        let raw = br#"{"error":{"type":"insufficient_quota","code":"insufficient_quota","message":"quota"}}"#.to_vec();
+        let err = parse_api_error(reqwest::StatusCode::TOO_MANY_REQUESTS, None, raw, false);
+        assert!(!err.is_rate_limited());
+        assert!(!err.is_retryable());
+    }
+
+    /// A body that yields `items` and counts how many were read.
+    struct Chunks {
+        items: std::collections::VecDeque<Result<Bytes>>,
+        read: std::sync::Arc<std::sync::atomic::AtomicUsize>,
+    }
+
+    impl futures_core::Stream for Chunks {
+        type Item = Result<Bytes>;
+
+        fn poll_next(
+            mut self: std::pin::Pin<&mut Self>,
+            _cx: &mut std::task::Context<'_>,
+        ) -> std::task::Poll<Option<Self::Item>> {
+            self.read.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            std::task::Poll::Ready(self.items.pop_front())
+        }
+    }
+
+    fn too_many_requests(
+        items: Vec<Result<Bytes>>,
+    ) -> (Response, std::sync::Arc<std::sync::atomic::AtomicUsize>) {
+        let read = std::sync::Arc::default();
+        let body = Body::from_stream(Chunks {
+            items: items.into(),
+            read: std::sync::Arc::clone(&read),
+        });
+        let resp = ::http::Response::builder().status(429).body(body).unwrap();
+        (Response::from_http(resp), read)
+    }
+
+    #[tokio::test]
+    async fn quota_check_reads_a_capped_prefix_and_keeps_the_body() {
+        let quota = br#"{"error":{"code":"insufficient_quota","message":"#.to_vec();
+        let chunks = vec![
+            Ok(Bytes::from(quota.clone())),
+            Ok(Bytes::from(vec![b'x'; MAX_ERROR_BODY_BYTES])),
+            Ok(Bytes::from_static(b"never read")),
+        ];
+        let total: usize = chunks.iter().map(|c| c.as_ref().unwrap().len()).sum();
+        let (mut resp, read) = too_many_requests(chunks);
+
+        // The truncated body isn't valid JSON, so this can't be told apart
+        // from a rate limit.
+        assert!(!is_quota_exceeded(&mut resp).await);
+        assert_eq!(read.load(std::sync::atomic::Ordering::SeqCst), 2);
+        assert_eq!(resp.bytes().await.unwrap().len(), total);
+    }
+
+    #[tokio::test]
+    async fn quota_check_detects_quota_errors_and_tolerates_read_errors() {
+        let body = br#"{"error":{"code":"insufficient_quota","message":"quota"}}"#;
+        let (mut resp, _) = too_many_requests(vec![Ok(Bytes::from_static(body))]);
+        assert!(is_quota_exceeded(&mut resp).await);
+        assert_eq!(resp.bytes().await.unwrap(), &body[..]);
+
+        let (mut resp, _) = too_many_requests(vec![Err(TransportError::retryable("reset").into())]);
+        assert!(!is_quota_exceeded(&mut resp).await);
+    }
+
+    #[test]
+    fn join_segments_encodes_each_segment() {
+        let base = url::Url::parse("https://example.com/v1").unwrap();
+        let joined = join_segments(&base, &["responses", "resp/1?x"]).unwrap();
+        assert_eq!(
+            joined.as_str(),
+            "https://example.com/v1/responses/resp%2F1%3Fx"
+        );
+    }
 }
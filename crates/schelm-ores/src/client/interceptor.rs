@@ -0,0 +1,251 @@
+//! Hooks for inspecting and modifying requests and responses.
+//!
+//! Interceptors are registered via
+//! [`ClientBuilder::interceptor`](crate::client::ClientBuilder::interceptor)
+//! and run for every request the client sends, including each retry attempt.
+
+use crate::client::{Error, ResponseMeta, Result, http};
+use crate::models::StreamingEvent;
+
+use bytes::Bytes;
+use reqwest::header::HeaderMap;
+
+use std::sync::Arc;
+
+/// A hook into the request lifecycle of a [`Client`](crate::client::Client).
+///
+/// All methods have no-op defaults, so implementations only override what
+/// they need.
+///
+/// # Order
+///
+/// [`on_request`](Self::on_request) runs in registration order, so later
+/// interceptors see the changes of earlier ones. All other hooks run in
+/// reverse registration order, so the first interceptor registered is the
+/// outermost one: it is the first to see the request and the last to see
+/// the response.
+pub trait Interceptor: Send + Sync {
+    /// Called before each attempt to send a request.
+    ///
+    /// Credentials, such as `Authorization`, and client-wide default headers
+    /// are added after all interceptors ran and only where the request doesn't
+    /// already set them. Returning an error aborts the request with that error.
+    fn on_request(&self, request: &mut InterceptedRequest) -> Result<()> {
+        let _ = request;
+        Ok(())
+    }
+
+    /// Called when the response headers of an attempt arrive, whatever the
+    /// status code.
+    fn on_response(&self, request: &RequestInfo, response: &ResponseMeta) {
+        let _ = (request, response);
+    }
+
+    /// Called when an attempt fails without a response, or when reading an
+    /// event stream fails.
+    fn on_error(&self, request: &RequestInfo, error: &Error) {
+        let _ = (request, error);
+    }
+
+    /// Called for every event decoded from an event stream.
+    fn on_stream_event(&self, request: &RequestInfo, event: &StreamingEvent) {
+        let _ = (request, event);
+    }
+}
+
+/// Identifies the request a response, error or event belongs to.
+#[derive(Clone, Debug)]
+pub struct RequestInfo {
+    /// The HTTP method.
+    pub method: reqwest::Method,
+    /// The URL the request was sent to, after all interceptors ran.
+    pub url: url::Url,
+    /// The attempt number, starting at 1.
+    pub attempt: u32,
+    /// The `Idempotency-Key` header, after all interceptors ran.
+    ///
+    /// Retries of a request share the same key.
+    pub idempotency_key: Option<String>,
+}
+
+/// An outgoing request, as seen by [`Interceptor::on_request`].
+#[derive(Debug)]
+pub struct InterceptedRequest {
+    method: reqwest::Method,
+    url: url::Url,
+    headers: HeaderMap,
+    body: Body,
+    attempt: u32,
+}
+
+#[derive(Debug)]
+enum Body {
+    Empty,
+    Json {
+        value: serde_json::Value,
+        modified: bool,
+        original: Bytes,
+    },
+    Raw(Bytes),
+}
+
+impl InterceptedRequest {
+    /// The HTTP method.
+    pub fn method(&self) -> &reqwest::Method {
+        &self.method
+    }
+
+    /// The request URL.
+    pub fn url(&self) -> &url::Url {
+        &self.url
+    }
+
+    /// Mutable access to the request URL, e.g. to route to another host.
+    pub fn url_mut(&mut self) -> &mut url::Url {
+        &mut self.url
+    }
+
+    /// The request headers.
+    pub fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    /// Mutable access to the request headers.
+    pub fn headers_mut(&mut self) -> &mut HeaderMap {
+        &mut self.headers
+    }
+
+    /// The JSON request body, if the request has one.
+    pub fn json(&self) -> Option<&serde_json::Value> {
+        match &self.body {
+            Body::Json { value, .. } => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Mutable access to the JSON request body, if the request has one.
+    pub fn json_mut(&mut self) -> Option<&mut serde_json::Value> {
+        match &mut self.body {
+            Body::Json {
+                value, modified, ..
+            } => {
+                *modified = true;
+                Some(value)
+            }
+            _ => None,
+        }
+    }
+
+    /// The serialized request body, e.g. for computing a signature.
+    pub fn body_bytes(&self) -> Option<Bytes> {
+        match &self.body {
+            Body::Empty => None,
+            Body::Json { value, .. } => Some(serde_json::to_vec(value).ok()?.into()),
+            Body::Raw(bytes) => Some(bytes.clone()),
+        }
+    }
+
+    /// The attempt number, starting at 1.
+    pub fn attempt(&self) -> u32 {
+        self.attempt
+    }
+}
+
+/// The interceptors registered on a client.
+#[derive(Clone, Default)]
+pub(crate) struct Interceptors(Vec<Arc<dyn Interceptor>>);
+
+impl std::fmt::Debug for Interceptors {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Interceptors").field(&self.0.len()).finish()
+    }
+}
+
+impl Interceptors {
+    pub(crate) fn push(&mut self, interceptor: Arc<dyn Interceptor>) {
+        self.0.push(interceptor);
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Runs [`Interceptor::on_request`] and applies the changes to `request`.
+    pub(crate) fn before_request(
+        &self,
+        request: &mut http::Request,
+        attempt: u32,
+    ) -> Result<RequestInfo> {
+        let is_json = request
+            .headers
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|v| v.contains("json"));
+        let body = match request.body.take() {
+            None => Body::Empty,
+            Some(bytes) if is_json => match serde_json::from_slice(&bytes) {
+                Ok(value) => Body::Json {
+                    value,
+                    modified: false,
+                    original: bytes,
+                },
+                Err(_) => Body::Raw(bytes),
+            },
+            Some(bytes) => Body::Raw(bytes),
+        };
+
+        let mut intercepted = InterceptedRequest {
+            method: request.method.clone(),
+            url: request.url.clone(),
+            headers: std::mem::take(&mut request.headers),
+            body,
+            attempt,
+        };
+        let result = self
+            .0
+            .iter()
+            .try_for_each(|interceptor| interceptor.on_request(&mut intercepted));
+
+        request.headers = intercepted.headers;
+        request.url = intercepted.url.clone();
+        request.body = match intercepted.body {
+            Body::Empty => None,
+            Body::Json {
+                value,
+                modified: true,
+                ..
+            } => Some(serde_json::to_vec(&value)?.into()),
+            Body::Json { original, .. } | Body::Raw(original) => Some(original),
+        };
+        result?;
+
+        Ok(RequestInfo {
+            method: intercepted.method,
+            url: intercepted.url,
+            attempt,
+            idempotency_key: http::idempotency_key_of(&request.headers),
+        })
+    }
+
+    pub(crate) fn on_response(&self, request: &RequestInfo, resp: &http::Response) {
+        if self.is_empty() {
+            return;
+        }
+        let meta = ResponseMeta::from_response(resp);
+        for interceptor in self.0.iter().rev() {
+            interceptor.on_response(request, &meta);
+        }
+    }
+
+    pub(crate) fn on_error(&self, request: &RequestInfo, error: &Error) {
+        for interceptor in self.0.iter().rev() {
+            interceptor.on_error(request, error);
+        }
+    }
+
+    pub(crate) fn on_stream_event(&self, request: &RequestInfo, event: &StreamingEvent) {
+        for interceptor in self.0.iter().rev() {
+            interceptor.on_stream_event(request, event);
+        }
+    }
+}
@@ -0,0 +1,219 @@
+//! HTTP response metadata returned alongside parsed results.
+
+use crate::client::http;
+
+use std::time::Duration;
+
+/// A result together with the metadata of the HTTP response it was parsed from.
+///
+/// Returned by the `*_with_meta` variants of request builders.
+#[derive(Debug)]
+pub struct WithMeta<T> {
+    /// The parsed result.
+    pub data: T,
+    /// Metadata of the HTTP response.
+    pub meta: ResponseMeta,
+}
+
+/// Metadata of an HTTP response.
+#[derive(Clone, Debug)]
+pub struct ResponseMeta {
+    /// The HTTP status code.
+    pub status: reqwest::StatusCode,
+    /// The value of the `x-request-id` header, if present.
+    pub request_id: Option<String>,
+    /// Server-side processing time, from the `openai-processing-ms` header.
+    pub processing_time: Option<Duration>,
+    /// Rate-limit information from the `x-ratelimit-*` headers.
+    pub rate_limit: RateLimitInfo,
+    /// All response headers, for anything not covered by the fields above.
+    pub headers: reqwest::header::HeaderMap,
+}
+
+impl ResponseMeta {
+    pub(crate) fn from_response(resp: &http::Response) -> Self {
+        let headers = resp.headers();
+        Self {
+            status: resp.status(),
+            request_id: header_str(headers, "x-request-id").map(str::to_owned),
+            processing_time: header_str(headers, "openai-processing-ms")
+                .and_then(|v| v.trim().parse::<f64>().ok())
+                .and_then(|ms| Duration::try_from_secs_f64(ms / 1000.0).ok()),
+            rate_limit: RateLimitInfo::from_headers(headers),
+            headers: headers.clone(),
+        }
+    }
+}
+
+/// Rate-limit state reported by the server.
+///
+/// Every field is `None` if the corresponding header is missing or malformed.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RateLimitInfo {
+    /// Maximum number of requests per window (`x-ratelimit-limit-requests`).
+    pub limit_requests: Option<u64>,
+    /// Maximum number of tokens per window (`x-ratelimit-limit-tokens`).
+    pub limit_tokens: Option<u64>,
+    /// Requests left in the current window (`x-ratelimit-remaining-requests`).
+    pub remaining_requests: Option<u64>,
+    /// Tokens left in the current window (`x-ratelimit-remaining-tokens`).
+    pub remaining_tokens: Option<u64>,
+    /// Time until the request limit resets (`x-ratelimit-reset-requests`).
+    pub reset_requests: Option<Duration>,
+    /// Time until the token limit resets (`x-ratelimit-reset-tokens`).
+    pub reset_tokens: Option<Duration>,
+}
+
+impl RateLimitInfo {
+    pub(crate) fn from_headers(headers: &reqwest::header::HeaderMap) -> Self {
+        let count = |name: &str| header_str(headers, name).and_then(|v| v.trim().parse().ok());
+        let reset = |name: &str| header_str(headers, name).and_then(parse_reset);
+        Self {
+            limit_requests: count("x-ratelimit-limit-requests"),
+            limit_tokens: count("x-ratelimit-limit-tokens"),
+            remaining_requests: count("x-ratelimit-remaining-requests"),
+            remaining_tokens: count("x-ratelimit-remaining-tokens"),
+            reset_requests: reset("x-ratelimit-reset-requests"),
+            reset_tokens: reset("x-ratelimit-reset-tokens"),
+        }
+    }
+}
+
+fn header_str<'h>(headers: &'h reqwest::header::HeaderMap, name: &str) -> Option<&'h str> {
+    headers.get(name).and_then(|v| v.to_str().ok())
+}
+
+/// Parses reset durations such as `20ms`, `1s`, `6m0s` or `1h2m3.5s`.
+///
+/// A bare number is taken as seconds.
+fn parse_reset(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(secs) = value.parse::<f64>() {
+        return Duration::try_from_secs_f64(secs).ok();
+    }
+
+    let mut total = 0.0;
+    let mut rest = value;
+    while !rest.is_empty() {
+        let split = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .filter(|&i| i > 0)?;
+        let number: f64 = rest[..split].parse().ok()?;
+        rest = &rest[split..];
+
+        let unit_len = rest
+            .find(|c: char| c.is_ascii_digit() || c == '.')
+            .unwrap_or(rest.len());
+        let factor = match &rest[..unit_len] {
+            "ms" => 0.001,
+            "s" => 1.0,
+            "m" => 60.0,
+            "h" => 3600.0,
+            _ => return None,
+        };
+        rest = &rest[unit_len..];
+        total += number * factor;
+    }
+
+    if value.is_empty() {
+        return None;
+    }
+    Duration::try_from_secs_f64(total).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::{HeaderMap, HeaderValue};
+
+    #[test]
+    fn parse_reset_handles_compound_durations() {
+        assert_eq!(parse_reset("20ms"), Some(Duration::from_millis(20)));
+        assert_eq!(parse_reset("1s"), Some(Duration::from_secs(1)));
+        assert_eq!(parse_reset("6m0s"), Some(Duration::from_secs(360)));
+        assert_eq!(
+            parse_reset("1h2m3.5s"),
+            Some(Duration::from_secs_f64(3723.5))
+        );
+        assert_eq!(parse_reset("2.5"), Some(Duration::from_secs_f64(2.5)));
+        assert_eq!(parse_reset(""), None);
+        assert_eq!(parse_reset("soon"), None);
+        assert_eq!(parse_reset("5d"), None);
+    }
+
+    #[test]
+    fn parse_reset_rejects_negative_and_overflowing_values() {
+        assert_eq!(parse_reset("-1"), None);
+        assert_eq!(parse_reset("NaN"), None);
+        assert_eq!(parse_reset("1e300"), None);
+        assert_eq!(parse_reset(&format!("{}h", "9".repeat(320))), None);
+    }
+
+    #[test]
+    fn processing_time_ignores_negative_and_overflowing_values() {
+        let meta = |value: &'static str| {
+            let resp = ::http::Response::builder()
+                .header("openai-processing-ms", value)
+                .body(crate::client::transport::Body::from(""))
+                .unwrap();
+            ResponseMeta::from_response(&http::Response::from_http(resp))
+        };
+        assert_eq!(
+            meta("125").processing_time,
+            Some(Duration::from_millis(125))
+        );
+        assert_eq!(meta("-5").processing_time, None);
+        assert_eq!(meta("1e300").processing_time, None);
+    }
+
+    #[test]
+    fn rate_limit_info_reads_all_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "x-ratelimit-limit-requests",
+            HeaderValue::from_static("500"),
+        );
+        headers.insert(
+            "x-ratelimit-limit-tokens",
+            HeaderValue::from_static("30000"),
+        );
+        headers.insert(
+            "x-ratelimit-remaining-requests",
+            HeaderValue::from_static("499"),
+        );
+        headers.insert(
+            "x-ratelimit-remaining-tokens",
+            HeaderValue::from_static("29980"),
+        );
+        headers.insert(
+            "x-ratelimit-reset-requests",
+            HeaderValue::from_static("120ms"),
+        );
+        headers.insert("x-ratelimit-reset-tokens", HeaderValue::from_static("40ms"));
+
+        assert_eq!(
+            RateLimitInfo::from_headers(&headers),
+            RateLimitInfo {
+                limit_requests: Some(500),
+                limit_tokens: Some(30000),
+                remaining_requests: Some(499),
+                remaining_tokens: Some(29980),
+                reset_requests: Some(Duration::from_millis(120)),
+                reset_tokens: Some(Duration::from_millis(40)),
+            }
+        );
+    }
+
+    #[test]
+    fn rate_limit_info_ignores_malformed_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "x-ratelimit-limit-requests",
+            HeaderValue::from_static("many"),
+        );
+        assert_eq!(
+            RateLimitInfo::from_headers(&headers),
+            RateLimitInfo::default()
+        );
+    }
+}
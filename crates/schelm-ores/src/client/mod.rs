@@ -2,22 +2,62 @@
 //!
 //! This module is behind the Cargo feature `client`.
 
+#[cfg(feature = "blocking")]
+pub mod blocking;
 pub mod endpoints;
 
+mod breaker;
 mod builder;
+mod cancel;
+mod config;
+mod credentials;
 mod error;
 mod http;
+mod interceptor;
+mod meta;
+mod pagination;
+mod pool;
+mod retry;
+#[cfg(feature = "tower")]
+mod service;
 pub(crate) mod sse;
+mod transport;
+mod validation;
 
+pub use breaker::{CircuitBreaker, CircuitState, CircuitStateChange};
 pub use builder::ClientBuilder;
-pub use error::{Error, Result, StreamingError};
+pub use cancel::CancellationToken;
+#[cfg(feature = "profiles")]
+pub use config::{Profile, Profiles};
+pub use credentials::{
+    AccessToken, AuthScheme, Credential, CredentialProvider, EnvCredentials, FileCredentials,
+    RefreshingCredentials, StaticCredentials,
+};
+pub use error::{ApiError, Error, FieldError, Result, StreamingError, TransportError};
+pub use interceptor::{InterceptedRequest, Interceptor, RequestInfo};
+pub use meta::{RateLimitInfo, ResponseMeta, WithMeta};
+pub use pagination::{ListParams, ListRequestBuilder, Order, Page, PageStream};
+pub use pool::{ClientPool, ClientPoolBuilder, Selection};
+pub use retry::RetryPolicy;
+#[cfg(feature = "tower")]
+pub use service::{BoxError, ResponsesService};
 pub use sse::ResponseEventStream;
+pub use transport::{Body, BoxFuture, InMemoryTransport, ReqwestTransport, Transport};
 
-/// Reqwest-based API client.
+/// API client.
+///
+/// Requests are sent through a [`Transport`], which is reqwest-based by default.
 #[derive(Clone, Debug)]
 pub struct Client {
     base_url: url::Url,
-    http: reqwest::Client,
+    transport: transport::SharedTransport,
+    credentials: credentials::SharedCredentials,
+    default_headers: reqwest::header::HeaderMap,
+    retry_policy: RetryPolicy,
+    interceptors: interceptor::Interceptors,
+    circuit_breaker: Option<CircuitBreaker>,
+    /// Set on the client handed out by a [`ClientPool`].
+    pool: Option<std::sync::Arc<pool::Pool>>,
 }
 
 impl Client {
@@ -26,16 +66,179 @@ impl Client {
         ClientBuilder::new(api_key, base_url)
     }
 
+    /// Creates a client configured by environment variables.
+    ///
+    /// See [`ClientBuilder::from_env`] for the variables read.
+    pub fn from_env() -> Result<Client> {
+        ClientBuilder::from_env()?.build()
+    }
+
     /// Access the Responses endpoint group.
     pub fn responses(&self) -> endpoints::responses::Responses<'_> {
         endpoints::responses::Responses::new(self)
     }
 
-    pub(crate) fn http(&self) -> &reqwest::Client {
-        &self.http
+    /// Access the Conversations endpoint group.
+    pub fn conversations(&self) -> endpoints::conversations::Conversations<'_> {
+        endpoints::conversations::Conversations::new(self)
+    }
+
+    /// Access the Files endpoint group.
+    pub fn files(&self) -> endpoints::files::Files<'_> {
+        endpoints::files::Files::new(self)
+    }
+
+    /// Access the Models endpoint group.
+    pub fn models(&self) -> endpoints::models::Models<'_> {
+        endpoints::models::Models::new(self)
+    }
+
+    pub(crate) fn get(&self, url: url::Url) -> http::RequestBuilder {
+        http::RequestBuilder::new(::http::Method::GET, url)
+    }
+
+    pub(crate) fn post(&self, url: url::Url) -> http::RequestBuilder {
+        http::RequestBuilder::new(::http::Method::POST, url)
+    }
+
+    pub(crate) fn delete(&self, url: url::Url) -> http::RequestBuilder {
+        http::RequestBuilder::new(::http::Method::DELETE, url)
+    }
+
+    /// Sends a request, retrying transient failures according to the [`RetryPolicy`].
+    ///
+    /// Returns as soon as the response headers arrive; unsuccessful statuses
+    /// that are not retried are returned as `Ok` for the caller to handle.
+    pub(crate) async fn execute(&self, request: http::RequestBuilder) -> Result<http::Response> {
+        let request = request.build()?;
+        match &self.pool {
+            Some(pool) => pool.execute(&self.base_url, request).await,
+            None => self.execute_request(request).await,
+        }
+    }
+
+    /// Like [`execute`](Self::execute), but ignores the pool.
+    async fn execute_request(&self, request: http::Request) -> Result<http::Response> {
+        let policy = &self.retry_policy;
+        let mut retry = 0;
+
+        loop {
+            retry += 1;
+            if retry >= policy.attempts() {
+                return self.send_checked(request, retry).await;
+            }
+
+            match self.send_checked(request.clone(), retry).await {
+                Ok(mut resp) if policy.is_retryable_status(resp.status()) => {
+                    if http::is_quota_exceeded(&mut resp).await {
+                        return Ok(resp);
+                    }
+                    match policy.delay_for(resp.headers(), retry) {
+                        Some(delay) => tokio::time::sleep(delay).await,
+                        None => return Ok(resp),
+                    }
+                }
+                Ok(resp) => return Ok(resp),
+                Err(e) if policy.is_retryable_error(&e) => {
+                    tokio::time::sleep(policy.backoff(retry)).await;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Sends a single attempt through the circuit breaker, if there is one.
+    async fn send_checked(&self, request: http::Request, attempt: u32) -> Result<http::Response> {
+        let Some(breaker) = &self.circuit_breaker else {
+            return self.send_attempt(request, attempt).await;
+        };
+        let permit = breaker.acquire(breaker::CircuitKey::new(&self.base_url, &request))?;
+        let result = self.send_attempt(request, attempt).await;
+        permit.record(http::is_server_failure(&result));
+        result
+    }
+
+    /// Sends a single attempt, running the interceptors around it.
+    async fn send_attempt(
+        &self,
+        mut request: http::Request,
+        attempt: u32,
+    ) -> Result<http::Response> {
+        let info = if self.interceptors.is_empty() {
+            None
+        } else {
+            Some(self.interceptors.before_request(&mut request, attempt)?)
+        };
+
+        let idempotency_key = http::idempotency_key_of(&request.headers);
+        let result = match self.finish_request(request).await {
+            Ok(request) => self
+                .transport
+                .send(request)
+                .await
+                .map(http::Response::from_http),
+            Err(e) => Err(e),
+        };
+        let result = result.map(|mut resp| {
+            if let Some(key) = idempotency_key {
+                resp.extensions_mut().insert(http::SentIdempotencyKey(key));
+            }
+            resp
+        });
+        let Some(info) = info else {
+            return result;
+        };
+        match result {
+            Ok(mut resp) => {
+                self.interceptors.on_response(&info, &resp);
+                // Lets event streams report to the interceptors, too.
+                resp.extensions_mut().insert(info);
+                resp.extensions_mut().insert(self.interceptors.clone());
+                Ok(resp)
+            }
+            Err(e) => {
+                self.interceptors.on_error(&info, &e);
+                Err(e)
+            }
+        }
+    }
+
+    /// Adds the credential and default headers the request doesn't set itself.
+    async fn finish_request(
+        &self,
+        mut request: http::Request,
+    ) -> Result<::http::Request<bytes::Bytes>> {
+        let headers = &mut request.headers;
+        self.credentials.credential().await?.apply(headers);
+        for (name, value) in &self.default_headers {
+            if !headers.contains_key(name) {
+                headers.insert(name, value.clone());
+            }
+        }
+        request.into_http()
+    }
+
+    /// Wraps a successful streaming response in a [`ResponseEventStream`].
+    pub(crate) fn event_stream(&self, resp: http::Response) -> Result<ResponseEventStream> {
+        // Pooled requests are observed by the interceptors of the endpoint
+        // that sent them.
+        let request = resp.extensions().get::<RequestInfo>().cloned();
+        let interceptors = resp
+            .extensions()
+            .get::<interceptor::Interceptors>()
+            .cloned();
+        let stream = ResponseEventStream::from_response(resp)?;
+        Ok(match (request, interceptors) {
+            (Some(request), Some(interceptors)) => stream.observed_by(interceptors, request),
+            _ => stream,
+        })
     }
 
     pub(crate) fn endpoint_url(&self, path: &str) -> Result<url::Url> {
         http::join(&self.base_url, path)
     }
+
+    pub(crate) fn resource_url(&self, segments: &[&str]) -> Result<url::Url> {
+        http::join_segments(&self.base_url, segments)
+    }
 }
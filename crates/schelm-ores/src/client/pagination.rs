@@ -0,0 +1,341 @@
+//! Cursor-based pagination for list endpoints.
+//!
+//! List endpoints return a [`Page`] of items. A [`ListRequestBuilder`] fetches a
+//! single page via [`send`](ListRequestBuilder::send), or turns into a
+//! [`PageStream`] that lazily fetches follow-up pages as items are consumed.
+
+use crate::client::{Client, Result, http};
+
+use futures_core::Stream;
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+
+use std::collections::VecDeque;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// Sort order of the items in a list, by creation time.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Order {
+    /// Oldest items first.
+    Asc,
+    /// Newest items first.
+    Desc,
+}
+
+impl Order {
+    fn as_str(self) -> &'static str {
+        match self {
+            Order::Asc => "asc",
+            Order::Desc => "desc",
+        }
+    }
+}
+
+/// Cursor parameters accepted by list endpoints.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ListParams {
+    /// Only return items after the item with this ID.
+    pub after: Option<String>,
+    /// Only return items before the item with this ID.
+    pub before: Option<String>,
+    /// Maximum number of items per page.
+    pub limit: Option<u32>,
+    /// Sort order of the returned items.
+    pub order: Option<Order>,
+}
+
+impl ListParams {
+    fn apply(&self, url: &mut url::Url) {
+        let mut query = url.query_pairs_mut();
+        if let Some(after) = &self.after {
+            query.append_pair("after", after);
+        }
+        if let Some(before) = &self.before {
+            query.append_pair("before", before);
+        }
+        if let Some(limit) = self.limit {
+            query.append_pair("limit", &limit.to_string());
+        }
+        if let Some(order) = self.order {
+            query.append_pair("order", order.as_str());
+        }
+    }
+}
+
+/// A single page of items returned by a list endpoint.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct Page<T> {
+    /// The object type, which is always `list`.
+    pub object: String,
+    /// The items on this page.
+    pub data: Vec<T>,
+    /// The ID of the first item on this page.
+    #[serde(default)]
+    pub first_id: Option<String>,
+    /// The ID of the last item on this page.
+    #[serde(default)]
+    pub last_id: Option<String>,
+    /// Whether more items are available after this page.
+    #[serde(default)]
+    pub has_more: bool,
+}
+
+impl<T> Page<T> {
+    /// Returns the parameters to fetch the page following this one, if any.
+    pub fn next_params(&self, current: &ListParams) -> Option<ListParams> {
+        if !self.has_more {
+            return None;
+        }
+        let last_id = self.last_id.clone()?;
+        Some(ListParams {
+            after: Some(last_id),
+            before: None,
+            ..current.clone()
+        })
+    }
+}
+
+/// Request builder shared by all list endpoints.
+///
+/// Endpoint-specific query parameters are added by the endpoint that creates
+/// the builder; the cursor parameters are appended per page.
+#[derive(Debug)]
+pub struct ListRequestBuilder<'a, T> {
+    client: &'a Client,
+    target: ListTarget,
+    params: ListParams,
+    _item: PhantomData<fn() -> T>,
+}
+
+/// The path and fixed query of a list endpoint.
+#[derive(Clone, Debug)]
+struct ListTarget {
+    segments: Vec<String>,
+    query: Vec<(String, String)>,
+}
+
+impl ListTarget {
+    fn url(&self, client: &Client, params: &ListParams) -> Result<url::Url> {
+        let segments: Vec<&str> = self.segments.iter().map(String::as_str).collect();
+        let mut url = client.resource_url(&segments)?;
+        if !self.query.is_empty() {
+            url.query_pairs_mut().extend_pairs(&self.query);
+        }
+        params.apply(&mut url);
+        if url.query() == Some("") {
+            url.set_query(None);
+        }
+        Ok(url)
+    }
+}
+
+impl<'a, T> ListRequestBuilder<'a, T>
+where
+    T: DeserializeOwned + Send + 'static,
+{
+    pub(crate) fn new(client: &'a Client, segments: &[&str]) -> Self {
+        Self {
+            client,
+            target: ListTarget {
+                segments: segments.iter().map(|s| (*s).to_owned()).collect(),
+                query: Vec::new(),
+            },
+            params: ListParams::default(),
+            _item: PhantomData,
+        }
+    }
+
+    /// Only return items after the item with this ID.
+    pub fn after(mut self, id: impl Into<String>) -> Self {
+        self.params.after = Some(id.into());
+        self
+    }
+
+    /// Only return items before the item with this ID.
+    pub fn before(mut self, id: impl Into<String>) -> Self {
+        self.params.before = Some(id.into());
+        self
+    }
+
+    /// Sets the maximum number of items per page.
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.params.limit = Some(limit);
+        self
+    }
+
+    /// Sets the sort order of the returned items.
+    pub fn order(mut self, order: Order) -> Self {
+        self.params.order = Some(order);
+        self
+    }
+
+    pub(crate) fn query_pair(mut self, key: &str, value: &str) -> Self {
+        self.target.query.push((key.to_owned(), value.to_owned()));
+        self
+    }
+
+    /// Fetches a single page.
+    pub async fn send(self) -> Result<Page<T>> {
+        fetch_page(self.client, &self.target, &self.params).await
+    }
+
+    /// Returns a stream over all items, fetching pages lazily as it is polled.
+    pub fn into_stream(self) -> PageStream<T> {
+        PageStream {
+            client: self.client.clone(),
+            target: self.target,
+            next: Some(self.params),
+            items: VecDeque::new(),
+            pending: None,
+        }
+    }
+}
+
+async fn fetch_page<T: DeserializeOwned>(
+    client: &Client,
+    target: &ListTarget,
+    params: &ListParams,
+) -> Result<Page<T>> {
+    let url = target.url(client, params)?;
+
+    let resp = client.execute(client.get(url)).await?;
+
+    // Lists nested under a resource, such as a response's input items, report
+    // a missing parent as `NotFound`.
+    if target.segments.len() > 1 {
+        http::resource_json(resp).await
+    } else {
+        http::json(resp).await
+    }
+}
+
+type PageFuture<T> = Pin<Box<dyn Future<Output = (ListParams, Result<Page<T>>)> + Send>>;
+
+/// A stream over the items of a list endpoint, created via
+/// [`ListRequestBuilder::into_stream`].
+///
+/// Pages are fetched one at a time, only once the items of the previous page
+/// have been consumed. The stream ends after the first error.
+pub struct PageStream<T> {
+    client: Client,
+    target: ListTarget,
+    next: Option<ListParams>,
+    items: VecDeque<T>,
+    pending: Option<PageFuture<T>>,
+}
+
+// The items are only ever moved out of the buffer, never pinned.
+impl<T> Unpin for PageStream<T> {}
+
+impl<T> std::fmt::Debug for PageStream<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PageStream")
+            .field("path", &self.target.segments)
+            .field("next", &self.next)
+            .field("buffered", &self.items.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> Stream for PageStream<T>
+where
+    T: DeserializeOwned + Send + 'static,
+{
+    type Item = Result<T>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            if let Some(item) = this.items.pop_front() {
+                return Poll::Ready(Some(Ok(item)));
+            }
+
+            if this.pending.is_none() {
+                let Some(params) = this.next.take() else {
+                    return Poll::Ready(None);
+                };
+                let client = this.client.clone();
+                let target = this.target.clone();
+                this.pending = Some(Box::pin(async move {
+                    let page = fetch_page(&client, &target, &params).await;
+                    (params, page)
+                }));
+            }
+
+            let pending = this
+                .pending
+                .as_mut()
+                .expect("pending page future was just set");
+            let (params, page) = match pending.as_mut().poll(cx) {
+                Poll::Ready(output) => output,
+                Poll::Pending => return Poll::Pending,
+            };
+            this.pending = None;
+
+            match page {
+                Ok(page) => {
+                    this.next = page.next_params(&params);
+                    this.items.extend(page.data);
+                }
+                Err(e) => return Poll::Ready(Some(Err(e))),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn list_params_append_all_cursor_fields() {
+        let mut url = url::Url::parse("https://example.com/v1/items").unwrap();
+        ListParams {
+            after: Some("item_1".into()),
+            before: Some("item_9".into()),
+            limit: Some(20),
+            order: Some(Order::Desc),
+        }
+        .apply(&mut url);
+        assert_eq!(
+            url.as_str(),
+            "https://example.com/v1/items?after=item_1&before=item_9&limit=20&order=desc"
+        );
+    }
+
+    #[test]
+    fn next_params_continue_after_last_id() {
+        let page = Page {
+            object: "list".into(),
+            data: vec![1, 2],
+            first_id: Some("item_1".into()),
+            last_id: Some("item_2".into()),
+            has_more: true,
+        };
+        let current = ListParams {
+            before: Some("item_9".into()),
+            limit: Some(2),
+            ..ListParams::default()
+        };
+        let next = page.next_params(&current).expect("expected a next page");
+        assert_eq!(next.after.as_deref(), Some("item_2"));
+        assert_eq!(next.before, None);
+        assert_eq!(next.limit, Some(2));
+    }
+
+    #[test]
+    fn next_params_is_none_on_last_page() {
+        let page: Page<i32> = Page {
+            object: "list".into(),
+            data: vec![],
+            first_id: None,
+            last_id: None,
+            has_more: false,
+        };
+        assert!(page.next_params(&ListParams::default()).is_none());
+    }
+}
@@ -0,0 +1,465 @@
+//! Load balancing and failover across several client configurations.
+
+use crate::client::{Client, Error, Result, endpoints, http};
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// How a [`ClientPool`] picks the endpoint for a request.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Selection {
+    /// Takes turns, in the order the endpoints were added.
+    #[default]
+    RoundRobin,
+    /// Takes turns in proportion to the endpoint weights.
+    Weighted,
+    /// Picks the endpoint with the fewest requests in flight.
+    LeastLoaded,
+}
+
+/// A set of clients that share the load of one logical client.
+///
+/// Each endpoint is a fully configured [`Client`], so endpoints can differ in
+/// base URL, credentials, retry policy and everything else. Every request
+/// goes to one endpoint, chosen by the pool's [`Selection`]. If it can't be
+/// reached or answers with a `5xx` response, the request is sent to the next
+/// endpoint; retries within an endpoint follow that endpoint's own
+/// [`RetryPolicy`]. Other errors, such as `409` conflicts or `429` rate and
+/// quota limits, are returned without failing over.
+///
+/// Endpoints are marked unhealthy passively: after
+/// [`failure_threshold`](ClientPoolBuilder::failure_threshold) consecutive
+/// connection errors or `5xx` responses, an endpoint is ejected from the
+/// pool for a backoff period that doubles with every further ejection. If
+/// all endpoints are ejected, the one that is readmitted first is used anyway.
+///
+/// The endpoint groups route every request through the pool. Since the
+/// endpoints don't share state, endpoints that address stored resources, such
+/// as [`Responses::retrieve`](endpoints::responses::Responses::retrieve),
+/// only work when the resource is visible through every endpoint.
+///
+/// [`RetryPolicy`]: crate::client::RetryPolicy
+#[derive(Clone, Debug)]
+pub struct ClientPool {
+    client: Client,
+}
+
+impl ClientPool {
+    /// Creates an empty [`ClientPoolBuilder`].
+    pub fn builder() -> ClientPoolBuilder {
+        ClientPoolBuilder::default()
+    }
+
+    /// Access the Responses endpoint group.
+    pub fn responses(&self) -> endpoints::responses::Responses<'_> {
+        self.client.responses()
+    }
+
+    /// Access the Conversations endpoint group.
+    pub fn conversations(&self) -> endpoints::conversations::Conversations<'_> {
+        self.client.conversations()
+    }
+
+    /// Access the Files endpoint group.
+    pub fn files(&self) -> endpoints::files::Files<'_> {
+        self.client.files()
+    }
+
+    /// Access the Models endpoint group.
+    pub fn models(&self) -> endpoints::models::Models<'_> {
+        self.client.models()
+    }
+
+    /// Returns a [`Client`] that routes its requests through the pool, for
+    /// code that expects a single client.
+    pub fn as_client(&self) -> &Client {
+        &self.client
+    }
+}
+
+/// Builder for [`ClientPool`].
+#[derive(Debug)]
+pub struct ClientPoolBuilder {
+    endpoints: Vec<(Client, u32)>,
+    selection: Selection,
+    failure_threshold: u32,
+    base_ejection_time: Duration,
+    max_ejection_time: Duration,
+}
+
+impl Default for ClientPoolBuilder {
+    /// Round-robin selection; endpoints are ejected for 5s after 3
+    /// consecutive failures, for at most 60s.
+    fn default() -> Self {
+        Self {
+            endpoints: Vec::new(),
+            selection: Selection::default(),
+            failure_threshold: 3,
+            base_ejection_time: Duration::from_secs(5),
+            max_ejection_time: Duration::from_secs(60),
+        }
+    }
+}
+
+impl ClientPoolBuilder {
+    /// Adds an endpoint with weight `1`.
+    pub fn endpoint(self, client: Client) -> Self {
+        self.weighted_endpoint(client, 1)
+    }
+
+    /// Adds an endpoint with the given weight, which only matters for
+    /// [`Selection::Weighted`]. A weight of `0` is treated as `1`.
+    pub fn weighted_endpoint(mut self, client: Client, weight: u32) -> Self {
+        self.endpoints.push((client, weight.max(1)));
+        self
+    }
+
+    /// Sets how endpoints are picked. Defaults to [`Selection::RoundRobin`].
+    pub fn selection(mut self, selection: Selection) -> Self {
+        self.selection = selection;
+        self
+    }
+
+    /// Sets the number of consecutive failures after which an endpoint is ejected.
+    pub fn failure_threshold(mut self, failures: u32) -> Self {
+        self.failure_threshold = failures.max(1);
+        self
+    }
+
+    /// Sets how long an endpoint is ejected the first time. It doubles with
+    /// every further ejection until the endpoint succeeds again.
+    pub fn base_ejection_time(mut self, duration: Duration) -> Self {
+        self.base_ejection_time = duration;
+        self
+    }
+
+    /// Sets the upper bound for the ejection time.
+    pub fn max_ejection_time(mut self, duration: Duration) -> Self {
+        self.max_ejection_time = duration;
+        self
+    }
+
+    /// Builds the pool. Fails if no endpoint was added.
+    pub fn build(self) -> Result<ClientPool> {
+        let Some((first, _)) = self.endpoints.first() else {
+            return Err(Error::Config("client pool has no endpoints".to_owned()));
+        };
+        let n = self.endpoints.len();
+        let mut client = first.clone();
+        client.pool = Some(Arc::new(Pool {
+            endpoints: self
+                .endpoints
+                .into_iter()
+                .map(|(client, weight)| Endpoint {
+                    client,
+                    weight,
+                    in_flight: AtomicUsize::new(0),
+                })
+                .collect(),
+            selection: self.selection,
+            failure_threshold: self.failure_threshold,
+            base_ejection_time: self.base_ejection_time,
+            max_ejection_time: self.max_ejection_time,
+            next: AtomicUsize::new(0),
+            state: Mutex::new(State {
+                health: vec![Health::default(); n],
+                current_weights: vec![0; n],
+            }),
+        }));
+        Ok(ClientPool { client })
+    }
+}
+
+/// The shared state of a [`ClientPool`], attached to the client it hands out.
+#[derive(Debug)]
+pub(crate) struct Pool {
+    endpoints: Vec<Endpoint>,
+    selection: Selection,
+    failure_threshold: u32,
+    base_ejection_time: Duration,
+    max_ejection_time: Duration,
+    /// The round-robin position.
+    next: AtomicUsize,
+    state: Mutex<State>,
+}
+
+#[derive(Debug)]
+struct Endpoint {
+    client: Client,
+    weight: u32,
+    in_flight: AtomicUsize,
+}
+
+#[derive(Debug)]
+struct State {
+    health: Vec<Health>,
+    /// The running weights of the smooth weighted round-robin.
+    current_weights: Vec<i64>,
+}
+
+#[derive(Clone, Debug, Default)]
+struct Health {
+    consecutive_failures: u32,
+    ejections: u32,
+    ejected_until: Option<Instant>,
+}
+
+/// Counts a request as in flight until dropped.
+struct InFlight<'a>(&'a AtomicUsize);
+
+impl<'a> InFlight<'a> {
+    fn new(counter: &'a AtomicUsize) -> Self {
+        counter.fetch_add(1, Ordering::SeqCst);
+        Self(counter)
+    }
+}
+
+impl Drop for InFlight<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+impl Pool {
+    /// Sends `request` to the selected endpoint, failing over to the others
+    /// when it can't be reached or fails with a `5xx` response.
+    ///
+    /// `base_url` is the base URL the request URL was built from.
+    pub(crate) async fn execute(
+        &self,
+        base_url: &url::Url,
+        request: http::Request,
+    ) -> Result<http::Response> {
+        let order = self.order(Instant::now());
+        let (&last, rest) = order.split_last().expect("pool has endpoints");
+
+        for &index in rest {
+            match self.send(index, base_url, request.clone()).await {
+                Err(Error::CircuitOpen { .. }) => {}
+                result if http::is_server_failure(&result) => {}
+                result => return result,
+            }
+        }
+        self.send(last, base_url, request).await
+    }
+
+    /// Sends `request` to a single endpoint and records the outcome.
+    async fn send(
+        &self,
+        index: usize,
+        base_url: &url::Url,
+        mut request: http::Request,
+    ) -> Result<http::Response> {
+        let endpoint = &self.endpoints[index];
+        request.url = rebase(&request.url, base_url, &endpoint.client.base_url)?;
+
+        let result = {
+            let _in_flight = InFlight::new(&endpoint.in_flight);
+            endpoint.client.execute_request(request).await
+        };
+        self.record(index, !http::is_server_failure(&result), Instant::now());
+        result
+    }
+
+    /// Returns the endpoints to try, in order.
+    ///
+    /// The selected endpoint comes first, followed by the other healthy
+    /// endpoints in round-robin order and finally the ejected ones, starting
+    /// with the one that is readmitted first.
+    fn order(&self, now: Instant) -> Vec<usize> {
+        let mut state = self.lock_state();
+        let n = self.endpoints.len();
+        let start = self.next.fetch_add(1, Ordering::Relaxed) % n;
+
+        let (mut healthy, mut ejected): (Vec<_>, Vec<_>) =
+            (0..n).map(|i| (start + i) % n).partition(|&i| {
+                state.health[i]
+                    .ejected_until
+                    .is_none_or(|until| until <= now)
+            });
+        ejected.sort_by_key(|&i| state.health[i].ejected_until);
+
+        let selected = match self.selection {
+            Selection::RoundRobin => None,
+            Selection::LeastLoaded => healthy.iter().position(|&i| {
+                let load = |i: usize| self.endpoints[i].in_flight.load(Ordering::SeqCst);
+                healthy.iter().all(|&other| load(i) <= load(other))
+            }),
+            Selection::Weighted => {
+                let total: i64 = healthy
+                    .iter()
+                    .map(|&i| i64::from(self.endpoints[i].weight))
+                    .sum();
+                for &i in &healthy {
+                    state.current_weights[i] += i64::from(self.endpoints[i].weight);
+                }
+                let best = healthy
+                    .iter()
+                    .enumerate()
+                    .max_by_key(|&(_, &i)| (state.current_weights[i], std::cmp::Reverse(i)))
+                    .map(|(pos, _)| pos);
+                if let Some(pos) = best {
+                    state.current_weights[healthy[pos]] -= total;
+                }
+                best
+            }
+        };
+        if let Some(pos) = selected {
+            let index = healthy.remove(pos);
+            healthy.insert(0, index);
+        }
+        healthy.append(&mut ejected);
+        healthy
+    }
+
+    /// Updates the health of an endpoint after a request.
+    fn record(&self, index: usize, success: bool, now: Instant) {
+        let mut state = self.lock_state();
+        let health = &mut state.health[index];
+        if success {
+            *health = Health::default();
+            return;
+        }
+        health.consecutive_failures += 1;
+        if health.consecutive_failures >= self.failure_threshold {
+            health.ejections += 1;
+            let exponent = (health.ejections - 1).min(31);
+            let duration = self
+                .base_ejection_time
+                .saturating_mul(1 << exponent)
+                .min(self.max_ejection_time);
+            health.ejected_until = Some(now + duration);
+            health.consecutive_failures = 0;
+        }
+    }
+
+    fn lock_state(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Moves `url` from below `from` to below `to`, keeping the relative path
+/// and the query.
+///
+/// URLs outside `from`, e.g. next-page links to another host, are kept.
+fn rebase(url: &url::Url, from: &url::Url, to: &url::Url) -> Result<url::Url> {
+    let from = http::normalize_base_url(from.clone());
+    match url.as_str().strip_prefix(from.as_str()) {
+        Some(relative) => Ok(http::normalize_base_url(to.clone()).join(relative)?),
+        None => Ok(url.clone()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool(selection: Selection, weights: &[u32]) -> Pool {
+        let mut builder = ClientPool::builder()
+            .selection(selection)
+            .failure_threshold(2)
+            .base_ejection_time(Duration::from_secs(1))
+            .max_ejection_time(Duration::from_secs(3));
+        for &weight in weights {
+            let client = Client::builder("key", "http://localhost/v1".parse().unwrap())
+                .build()
+                .unwrap();
+            builder = builder.weighted_endpoint(client, weight);
+        }
+        let pool = builder.build().unwrap().client.pool.unwrap();
+        Arc::into_inner(pool).unwrap()
+    }
+
+    fn picks(pool: &Pool, now: Instant, n: usize) -> Vec<usize> {
+        (0..n).map(|_| pool.order(now)[0]).collect()
+    }
+
+    fn is_ejected(pool: &Pool, index: usize, now: Instant) -> bool {
+        pool.lock_state().health[index]
+            .ejected_until
+            .is_some_and(|until| until > now)
+    }
+
+    #[test]
+    fn round_robin_takes_turns() {
+        let pool = pool(Selection::RoundRobin, &[1, 1, 1]);
+        let now = Instant::now();
+        assert_eq!(picks(&pool, now, 4), [0, 1, 2, 0]);
+        assert_eq!(pool.order(now), [1, 2, 0]);
+    }
+
+    #[test]
+    fn weighted_follows_weights() {
+        let pool = pool(Selection::Weighted, &[3, 1]);
+        let now = Instant::now();
+        assert_eq!(picks(&pool, now, 8), [0, 0, 1, 0, 0, 0, 1, 0]);
+    }
+
+    #[test]
+    fn least_loaded_prefers_idle_endpoints() {
+        let pool = pool(Selection::LeastLoaded, &[1, 1, 1]);
+        let now = Instant::now();
+        let _busy = InFlight::new(&pool.endpoints[0].in_flight);
+        let _busier = [
+            InFlight::new(&pool.endpoints[2].in_flight),
+            InFlight::new(&pool.endpoints[2].in_flight),
+        ];
+        assert_eq!(picks(&pool, now, 3), [1, 1, 1]);
+    }
+
+    #[test]
+    fn failing_endpoints_are_ejected_with_backoff() {
+        let pool = pool(Selection::RoundRobin, &[1, 1]);
+        let now = Instant::now();
+
+        pool.record(0, false, now);
+        assert!(!is_ejected(&pool, 0, now));
+        pool.record(0, false, now);
+        assert!(is_ejected(&pool, 0, now));
+        assert_eq!(picks(&pool, now, 2), [1, 1]);
+        assert_eq!(pool.order(now), [1, 0]);
+
+        // Readmitted after 1s, and ejected for 2s on the next failures.
+        let later = now + Duration::from_secs(1);
+        assert!(!is_ejected(&pool, 0, later));
+        pool.record(0, false, later);
+        pool.record(0, false, later);
+        assert!(is_ejected(&pool, 0, later + Duration::from_millis(1999)));
+        assert!(!is_ejected(&pool, 0, later + Duration::from_secs(2)));
+
+        // A success resets the backoff.
+        pool.record(0, true, later);
+        pool.record(0, false, later);
+        pool.record(0, false, later);
+        assert!(!is_ejected(&pool, 0, later + Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn ejected_endpoints_are_last_resort() {
+        let pool = pool(Selection::RoundRobin, &[1, 1]);
+        let now = Instant::now();
+        for _ in 0..2 {
+            pool.record(1, false, now);
+        }
+        for _ in 0..2 {
+            pool.record(0, false, now + Duration::from_millis(10));
+        }
+        assert_eq!(pool.order(now), [1, 0]);
+    }
+
+    #[test]
+    fn rebase_moves_urls_between_base_urls() {
+        let from = url::Url::parse("https://a.example.com/v1").unwrap();
+        let to = url::Url::parse("https://b.example.com/api/v1/").unwrap();
+
+        let url = url::Url::parse("https://a.example.com/v1/responses/resp%2F1?x=1").unwrap();
+        assert_eq!(
+            rebase(&url, &from, &to).unwrap().as_str(),
+            "https://b.example.com/api/v1/responses/resp%2F1?x=1"
+        );
+
+        let other = url::Url::parse("https://c.example.com/next").unwrap();
+        assert_eq!(rebase(&other, &from, &to).unwrap(), other);
+    }
+}
@@ -0,0 +1,245 @@
+//! Automatic retries with exponential backoff.
+
+use crate::client::Error;
+
+use std::hash::{BuildHasher, RandomState};
+use std::time::{Duration, SystemTime};
+
+/// The longest server-requested delay that is honored.
+///
+/// A longer `Retry-After` is treated as the server asking us not to retry at all.
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(60);
+
+/// Controls if and how failed requests are retried.
+///
+/// A request is retried when the connection fails or times out before a
+/// response arrives, or when the response has one of the
+/// [retryable status codes](Self::retryable_statuses). Between attempts, the
+/// client waits for the delay requested via the `retry-after-ms` or
+/// `Retry-After` response headers, or otherwise for an exponentially growing,
+/// jittered delay.
+///
+/// Streaming requests are only retried until the response headers arrive; an
+/// error while reading the event stream is never retried.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    base_delay: Duration,
+    max_delay: Duration,
+    jitter: f64,
+    retryable_statuses: Vec<reqwest::StatusCode>,
+}
+
+impl Default for RetryPolicy {
+    /// Three attempts, starting at 500ms and capped at 8s, with 25% jitter.
+    ///
+    /// Retries `408`, `409`, `429` and `5xx` gateway and server errors.
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(8),
+            jitter: 0.25,
+            retryable_statuses: [408, 409, 429, 500, 502, 503, 504]
+                .into_iter()
+                .map(|code| reqwest::StatusCode::from_u16(code).expect("valid status code"))
+                .collect(),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that never retries. This is what clients use by default.
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    /// Sets the total number of attempts, including the first one.
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Sets the delay before the first retry. It doubles with every further retry.
+    pub fn base_delay(mut self, delay: Duration) -> Self {
+        self.base_delay = delay;
+        self
+    }
+
+    /// Sets the upper bound for the computed delay between attempts.
+    pub fn max_delay(mut self, delay: Duration) -> Self {
+        self.max_delay = delay;
+        self
+    }
+
+    /// Sets the fraction of each delay that is randomized, between `0.0` and `1.0`.
+    ///
+    /// With a jitter of `0.25`, a computed delay of 1s becomes a random delay
+    /// between 750ms and 1s.
+    pub fn jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Sets the response status codes that trigger a retry.
+    pub fn retryable_statuses(mut self, statuses: Vec<reqwest::StatusCode>) -> Self {
+        self.retryable_statuses = statuses;
+        self
+    }
+
+    pub(crate) fn attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    pub(crate) fn is_retryable_status(&self, status: reqwest::StatusCode) -> bool {
+        self.retryable_statuses.contains(&status)
+    }
+
+    /// Whether `err` happened before a response arrived and may go away on
+    /// another attempt.
+    pub(crate) fn is_retryable_error(&self, err: &Error) -> bool {
+        match err {
+            Error::Reqwest(e) => e.is_connect() || e.is_timeout() || e.is_request(),
+            Error::Transport(e) => e.is_retryable(),
+            _ => false,
+        }
+    }
+
+    /// Returns the backoff delay before retry number `retry` (starting at 1).
+    pub(crate) fn backoff(&self, retry: u32) -> Duration {
+        let exponent = retry.saturating_sub(1).min(31);
+        let delay = self
+            .base_delay
+            .saturating_mul(1 << exponent)
+            .min(self.max_delay);
+        delay.mul_f64(1.0 - self.jitter * random_unit())
+    }
+
+    /// Returns the delay before retrying a response, or `None` if the server
+    /// asked for a longer delay than [`MAX_RETRY_AFTER`].
+    pub(crate) fn delay_for(
+        &self,
+        headers: &reqwest::header::HeaderMap,
+        retry: u32,
+    ) -> Option<Duration> {
+        match retry_after(headers) {
+            Some(delay) if delay > MAX_RETRY_AFTER => None,
+            Some(delay) => Some(delay),
+            None => Some(self.backoff(retry)),
+        }
+    }
+}
+
+/// Parses the server-requested delay from `retry-after-ms` or `Retry-After`.
+///
+/// `Retry-After` may hold either a number of seconds or an HTTP date.
+pub(crate) fn retry_after(headers: &reqwest::header::HeaderMap) -> Option<Duration> {
+    let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+
+    if let Some(delay) = header("retry-after-ms")
+        .and_then(|v| v.trim().parse::<f64>().ok())
+        .and_then(|ms| seconds(ms / 1000.0))
+    {
+        return Some(delay);
+    }
+
+    let value = header(reqwest::header::RETRY_AFTER.as_str())?.trim();
+    if let Ok(secs) = value.parse::<f64>() {
+        return seconds(secs);
+    }
+    let date = httpdate::parse_http_date(value).ok()?;
+    Some(
+        date.duration_since(SystemTime::now())
+            .unwrap_or(Duration::ZERO),
+    )
+}
+
+/// Converts a header's number of seconds, saturating values too large for a
+/// [`Duration`] so they still read as "too long to wait".
+fn seconds(secs: f64) -> Option<Duration> {
+    (secs.is_finite() && secs >= 0.0)
+        .then(|| Duration::try_from_secs_f64(secs).unwrap_or(Duration::MAX))
+}
+
+/// Returns a pseudo-random number in `[0, 1)`.
+///
+/// Every `RandomState` is seeded differently, which is plenty for jitter.
+pub(crate) fn random_unit() -> f64 {
+    let bits = RandomState::new().hash_one(SystemTime::now()) >> 11;
+    bits as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::{HeaderMap, HeaderValue};
+
+    #[test]
+    fn backoff_doubles_up_to_max_delay() {
+        let policy = RetryPolicy::default()
+            .base_delay(Duration::from_millis(100))
+            .max_delay(Duration::from_millis(350))
+            .jitter(0.0);
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff(3), Duration::from_millis(350));
+        assert_eq!(policy.backoff(40), Duration::from_millis(350));
+    }
+
+    #[test]
+    fn backoff_jitter_stays_within_bounds() {
+        let policy = RetryPolicy::default()
+            .base_delay(Duration::from_millis(1000))
+            .jitter(0.5);
+        for _ in 0..100 {
+            let delay = policy.backoff(1);
+            assert!(delay > Duration::from_millis(500), "delay was {delay:?}");
+            assert!(delay <= Duration::from_millis(1000), "delay was {delay:?}");
+        }
+    }
+
+    #[test]
+    fn retry_after_ms_takes_precedence() {
+        let mut headers = HeaderMap::new();
+        headers.insert("retry-after-ms", HeaderValue::from_static("250"));
+        headers.insert("retry-after", HeaderValue::from_static("5"));
+        assert_eq!(retry_after(&headers), Some(Duration::from_millis(250)));
+    }
+
+    #[test]
+    fn retry_after_parses_seconds_and_dates() {
+        let mut headers = HeaderMap::new();
+        headers.insert("retry-after", HeaderValue::from_static("2"));
+        assert_eq!(retry_after(&headers), Some(Duration::from_secs(2)));
+
+        headers.insert(
+            "retry-after",
+            HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"),
+        );
+        assert_eq!(retry_after(&headers), Some(Duration::ZERO));
+
+        headers.insert("retry-after", HeaderValue::from_static("soon"));
+        assert_eq!(retry_after(&headers), None);
+    }
+
+    #[test]
+    fn overlong_retry_after_disables_retry() {
+        let mut headers = HeaderMap::new();
+        headers.insert("retry-after", HeaderValue::from_static("3600"));
+        assert_eq!(RetryPolicy::default().delay_for(&headers, 1), None);
+    }
+
+    #[test]
+    fn huge_retry_after_does_not_panic() {
+        let mut headers = HeaderMap::new();
+        headers.insert("retry-after", HeaderValue::from_static("1e300"));
+        assert_eq!(retry_after(&headers), Some(Duration::MAX));
+        assert_eq!(RetryPolicy::default().delay_for(&headers, 1), None);
+
+        headers.insert("retry-after-ms", HeaderValue::from_static("1e300"));
+        assert_eq!(retry_after(&headers), Some(Duration::MAX));
+    }
+}
@@ -0,0 +1,159 @@
+//! [`tower`] integration.
+//!
+//! This module is behind the Cargo feature `tower`.
+
+use crate::client::endpoints::responses::CreateResponseRequestBuilder;
+use crate::client::{Body, BoxFuture, Client, Error, Result, Transport, TransportError};
+use crate::models;
+
+use bytes::Bytes;
+use futures_core::Stream;
+use tower::util::BoxCloneSyncService;
+use tower::{Service, ServiceExt};
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// The error type of the services accepted by
+/// [`ClientBuilder::http_service`](crate::client::ClientBuilder::http_service).
+pub type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// A [`Transport`] that sends requests through a type-erased tower service.
+///
+/// Service errors are returned as [`Error::Transport`]. They are retried
+/// like other connection failures if they were caused by an I/O error that
+/// may go away, such as a refused or reset connection or a timeout.
+#[derive(Clone)]
+pub(crate) struct TowerTransport(
+    BoxCloneSyncService<http::Request<Bytes>, http::Response<Body>, BoxError>,
+);
+
+impl std::fmt::Debug for TowerTransport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TowerTransport").finish_non_exhaustive()
+    }
+}
+
+impl TowerTransport {
+    pub(crate) fn new<S, B>(service: S) -> Self
+    where
+        S: Service<http::Request<Bytes>, Response = http::Response<B>>
+            + Clone
+            + Send
+            + Sync
+            + 'static,
+        S::Error: Into<BoxError>,
+        S::Future: Send + 'static,
+        B: http_body::Body + Send + 'static,
+        B::Data: Into<Bytes>,
+        B::Error: Into<BoxError>,
+    {
+        let service = service
+            .map_response(|resp: http::Response<B>| {
+                resp.map(|body| Body::from_stream(HttpBodyStream(Box::pin(body))))
+            })
+            .map_err(Into::into);
+        Self(BoxCloneSyncService::new(service))
+    }
+}
+
+impl Transport for TowerTransport {
+    fn send(&self, request: http::Request<Bytes>) -> BoxFuture<'_, Result<http::Response<Body>>> {
+        let service = self.0.clone();
+        Box::pin(async move {
+            service
+                .oneshot(request)
+                .await
+                .map_err(|e| Error::Transport(transport_error(e)))
+        })
+    }
+}
+
+/// Classifies a service error by the I/O error that caused it, if any.
+fn transport_error(err: BoxError) -> TransportError {
+    use std::io::ErrorKind;
+
+    let mut source: Option<&(dyn std::error::Error + 'static)> = Some(&*err);
+    while let Some(e) = source {
+        if let Some(io) = e.downcast_ref::<std::io::Error>()
+            && matches!(
+                io.kind(),
+                ErrorKind::ConnectionRefused
+                    | ErrorKind::ConnectionReset
+                    | ErrorKind::ConnectionAborted
+                    | ErrorKind::TimedOut
+            )
+        {
+            return TransportError::retryable(err);
+        }
+        source = e.source();
+    }
+    TransportError::new(err)
+}
+
+/// Adapts an [`http_body::Body`] to a stream of data chunks, skipping trailers.
+struct HttpBodyStream<B>(Pin<Box<B>>);
+
+impl<B> Stream for HttpBodyStream<B>
+where
+    B: http_body::Body,
+    B::Data: Into<Bytes>,
+    B::Error: Into<BoxError>,
+{
+    type Item = Result<Bytes>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            let frame = match self.0.as_mut().poll_frame(cx) {
+                Poll::Ready(Some(Ok(frame))) => frame,
+                Poll::Ready(Some(Err(e))) => {
+                    return Poll::Ready(Some(Err(TransportError::new(e).into())));
+                }
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            };
+            // Trailers carry no data.
+            if let Ok(data) = frame.into_data() {
+                return Poll::Ready(Some(Ok(data.into())));
+            }
+        }
+    }
+}
+
+/// The responses endpoint as a [`tower::Service`], created via
+/// [`Responses::service`](crate::client::endpoints::responses::Responses::service).
+///
+/// Each request body is sent like
+/// [`CreateResponseRequestBuilder::send`], including validation. The `stream`
+/// field is ignored, since the service always returns the full response.
+#[derive(Clone, Debug)]
+pub struct ResponsesService {
+    client: Client,
+}
+
+impl ResponsesService {
+    pub(crate) fn new(client: Client) -> Self {
+        Self { client }
+    }
+}
+
+impl Service<models::CreateResponseBody> for ResponsesService {
+    type Response = models::ResponseResource;
+    type Error = Error;
+    type Future = Pin<Box<dyn Future<Output = Result<models::ResponseResource>> + Send>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, mut body: models::CreateResponseBody) -> Self::Future {
+        let client = self.client.clone();
+        body.stream = None;
+        Box::pin(async move {
+            CreateResponseRequestBuilder::from_body(&client, body)
+                .send()
+                .await
+        })
+    }
+}
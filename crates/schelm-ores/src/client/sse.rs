@@ -2,15 +2,18 @@
 //!
 //! Consumes a byte stream and yields `Result<StreamingEvent>` items.
 
-use crate::client::Result;
 use crate::client::error::StreamingError;
+use crate::client::interceptor::Interceptors;
+use crate::client::transport::{Body, BoxFuture};
+use crate::client::{CancellationToken, Error, RequestInfo, Result, http};
 use crate::models::StreamingEvent;
 
-use bytes::Bytes;
 use futures_core::Stream;
 
+use std::future::Future;
 use std::pin::Pin;
 use std::task::{Context, Poll};
+use std::time::Duration;
 
 /// Maximum size of a single SSE event payload in bytes (1 MiB).
 const MAX_EVENT_BYTES: usize = 1024 * 1024;
@@ -19,42 +22,111 @@ const MAX_EVENT_BYTES: usize = 1024 * 1024;
 ///
 /// Created via [`ResponseEventStream::new`]. Implements [`futures_core::Stream`].
 pub struct ResponseEventStream {
-    inner: Pin<Box<dyn Stream<Item = Result<Bytes, reqwest::Error>> + Send>>,
+    inner: Body,
     buf: Vec<u8>,
     done: bool,
+    observer: Option<(Interceptors, RequestInfo)>,
+    idle: Option<IdleTimer>,
+    cancel: Option<BoxFuture<'static, ()>>,
+}
+
+/// Fires when no data arrived for a while.
+struct IdleTimer {
+    timeout: Duration,
+    /// Created on the first poll, so the stream can be set up outside a runtime.
+    sleep: Option<Pin<Box<tokio::time::Sleep>>>,
+}
+
+impl IdleTimer {
+    fn reset(&mut self) {
+        if let Some(sleep) = &mut self.sleep {
+            sleep
+                .as_mut()
+                .reset(tokio::time::Instant::now() + self.timeout);
+        }
+    }
+
+    fn poll_elapsed(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        let timeout = self.timeout;
+        self.sleep
+            .get_or_insert_with(|| Box::pin(tokio::time::sleep(timeout)))
+            .as_mut()
+            .poll(cx)
+    }
 }
 
 impl ResponseEventStream {
-    /// Creates a new `ResponseEventStream` from a reqwest response.
+    /// Creates a new `ResponseEventStream` from a response.
     ///
     /// Validates that the content-type is `text/event-stream` before constructing.
-    pub(crate) fn from_response(resp: reqwest::Response) -> Result<Self> {
+    pub(crate) fn from_response(resp: http::Response) -> Result<Self> {
         validate_content_type(&resp)?;
         Ok(Self {
-            inner: Box::pin(resp.bytes_stream()),
+            inner: resp.into_body(),
             buf: Vec::new(),
             done: false,
+            observer: None,
+            idle: None,
+            cancel: None,
         })
     }
 
     /// Creates a `ResponseEventStream` from any byte chunk stream.
     ///
-    /// Used internally for testing without reqwest.
+    /// Used internally for testing without a transport.
     #[cfg(test)]
     pub(crate) fn from_stream<S>(stream: S) -> Self
     where
-        S: Stream<Item = Result<Bytes, reqwest::Error>> + Send + 'static,
+        S: Stream<Item = Result<bytes::Bytes>> + Send + 'static,
     {
         Self {
-            inner: Box::pin(stream),
+            inner: Body::from_stream(stream),
             buf: Vec::new(),
             done: false,
+            observer: None,
+            idle: None,
+            cancel: None,
+        }
+    }
+
+    /// Ends the stream with [`StreamingError::IdleTimeout`] when no data,
+    /// including keepalives, arrives for `timeout`.
+    ///
+    /// Unlike a request timeout, this doesn't limit the total duration of the
+    /// stream.
+    pub fn idle_timeout(mut self, timeout: Duration) -> Self {
+        self.idle = Some(IdleTimer {
+            timeout,
+            sleep: None,
+        });
+        self
+    }
+
+    /// Ends the stream with [`Error::Cancelled`] once `token` is cancelled.
+    pub(crate) fn cancel_on(mut self, token: CancellationToken) -> Self {
+        self.cancel = Some(Box::pin(async move { token.cancelled().await }));
+        self
+    }
+
+    /// Reports decoded events and stream errors to the given interceptors.
+    pub(crate) fn observed_by(mut self, interceptors: Interceptors, request: RequestInfo) -> Self {
+        self.observer = Some((interceptors, request));
+        self
+    }
+
+    /// Notifies the interceptors, if any, about an item about to be returned.
+    fn observe(&self, item: &Result<StreamingEvent>) {
+        if let Some((interceptors, request)) = &self.observer {
+            match item {
+                Ok(event) => interceptors.on_stream_event(request, event),
+                Err(e) => interceptors.on_error(request, e),
+            }
         }
     }
 }
 
 /// Validates the `Content-Type` header of a response.
-fn validate_content_type(resp: &reqwest::Response) -> Result<()> {
+fn validate_content_type(resp: &http::Response) -> Result<()> {
     let ct = resp
         .headers()
         .get(reqwest::header::CONTENT_TYPE)
@@ -227,11 +299,29 @@ impl Stream for ResponseEventStream {
 
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         let this = self.get_mut();
+        let poll = this.poll_decode(cx);
+        if let Poll::Ready(Some(item)) = &poll {
+            this.observe(item);
+        }
+        poll
+    }
+}
+
+impl ResponseEventStream {
+    fn poll_decode(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<StreamingEvent>>> {
+        let this = self;
 
         if this.done {
             return Poll::Ready(None);
         }
 
+        if let Some(cancelled) = &mut this.cancel
+            && cancelled.as_mut().poll(cx).is_ready()
+        {
+            this.done = true;
+            return Poll::Ready(Some(Err(Error::Cancelled)));
+        }
+
         loop {
             // Try to extract a frame from the buffer first
             if let Some((frame, consumed)) = extract_frame(&this.buf) {
@@ -259,8 +349,11 @@ impl Stream for ResponseEventStream {
             }
 
             // Need more data — poll the inner stream
-            match this.inner.as_mut().poll_next(cx) {
+            match Pin::new(&mut this.inner).poll_next(cx) {
                 Poll::Ready(Some(Ok(chunk))) => {
+                    if let Some(idle) = &mut this.idle {
+                        idle.reset();
+                    }
                     this.buf.extend_from_slice(&chunk);
 
                     // Safety limit check
@@ -276,14 +369,25 @@ impl Stream for ResponseEventStream {
                 }
                 Poll::Ready(Some(Err(e))) => {
                     this.done = true;
-                    return Poll::Ready(Some(Err(e.into())));
+                    return Poll::Ready(Some(Err(e)));
                 }
                 Poll::Ready(None) => {
                     // Stream ended — check if there's remaining data without termination
                     this.done = true;
                     return Poll::Ready(None);
                 }
-                Poll::Pending => return Poll::Pending,
+                Poll::Pending => {
+                    if let Some(idle) = &mut this.idle
+                        && idle.poll_elapsed(cx).is_ready()
+                    {
+                        this.done = true;
+                        return Poll::Ready(Some(Err(StreamingError::IdleTimeout {
+                            timeout: idle.timeout,
+                        }
+                        .into())));
+                    }
+                    return Poll::Pending;
+                }
             }
         }
     }
@@ -292,6 +396,7 @@ impl Stream for ResponseEventStream {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use bytes::Bytes;
     use std::collections::VecDeque;
 
     /// A simple in-memory stream of byte chunks for testing.
@@ -308,7 +413,7 @@ mod tests {
     }
 
     impl Stream for TestStream {
-        type Item = std::result::Result<Bytes, reqwest::Error>;
+        type Item = Result<Bytes>;
 
         fn poll_next(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
             match self.get_mut().chunks.pop_front() {
@@ -628,4 +733,87 @@ mod tests {
             other => panic!("expected Unknown, got: {other:?}"),
         }
     }
+
+    // -----------------------------------------------------------------------
+    // 9. Idle timeout and cancellation
+    // -----------------------------------------------------------------------
+
+    /// Yields each chunk after its delay, then stalls forever.
+    struct SlowStream {
+        chunks: VecDeque<(std::time::Duration, Bytes)>,
+        sleep: Option<Pin<Box<tokio::time::Sleep>>>,
+    }
+
+    impl Stream for SlowStream {
+        type Item = Result<Bytes>;
+
+        fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+            let this = self.get_mut();
+            let Some((delay, _)) = this.chunks.front() else {
+                return Poll::Pending;
+            };
+            let delay = *delay;
+            let sleep = this
+                .sleep
+                .get_or_insert_with(|| Box::pin(tokio::time::sleep(delay)));
+            std::task::ready!(sleep.as_mut().poll(cx));
+            this.sleep = None;
+            let (_, chunk) = this.chunks.pop_front().unwrap();
+            Poll::Ready(Some(Ok(chunk)))
+        }
+    }
+
+    fn slow_stream(chunks: Vec<(u64, String)>) -> SlowStream {
+        SlowStream {
+            chunks: chunks
+                .into_iter()
+                .map(|(ms, chunk)| (Duration::from_millis(ms), Bytes::from(chunk)))
+                .collect(),
+            sleep: None,
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn idle_timeout_is_reset_by_keepalives() {
+        let stream = slow_stream(vec![
+            (0, sse_frame(None, &text_delta_json(0, "a"))),
+            (50, ": keepalive\n\n".to_owned()),
+            (50, ": keepalive\n\n".to_owned()),
+            (50, sse_frame(None, &text_delta_json(1, "b"))),
+        ]);
+        let mut event_stream =
+            ResponseEventStream::from_stream(stream).idle_timeout(Duration::from_millis(60));
+
+        assert!(next(&mut event_stream).await.unwrap().is_ok());
+        assert!(next(&mut event_stream).await.unwrap().is_ok());
+
+        let err = next(&mut event_stream).await.unwrap().unwrap_err();
+        assert!(
+            matches!(err, Error::Streaming(StreamingError::IdleTimeout { timeout })
+                if timeout == Duration::from_millis(60)),
+            "got: {err:?}"
+        );
+        assert!(next(&mut event_stream).await.is_none());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn cancellation_ends_the_stream() {
+        let stream = slow_stream(vec![
+            (0, sse_frame(None, &text_delta_json(0, "a"))),
+            (10_000, sse_frame(None, &text_delta_json(1, "b"))),
+        ]);
+        let token = CancellationToken::new();
+        let mut event_stream = ResponseEventStream::from_stream(stream).cancel_on(token.clone());
+
+        assert!(next(&mut event_stream).await.unwrap().is_ok());
+        let canceller = token.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            canceller.cancel();
+        });
+
+        let err = next(&mut event_stream).await.unwrap().unwrap_err();
+        assert!(matches!(err, Error::Cancelled), "got: {err:?}");
+        assert!(next(&mut event_stream).await.is_none());
+    }
 }
@@ -0,0 +1,278 @@
+//! Pluggable HTTP transports.
+//!
+//! Every request a [`Client`](crate::client::Client) sends goes through a
+//! [`Transport`]. The default is [`ReqwestTransport`]; [`InMemoryTransport`]
+//! answers requests without any I/O, which is handy in tests.
+
+use crate::client::http::RequestTimeout;
+use crate::client::{Error, Result, TransportError};
+
+use bytes::Bytes;
+use futures_core::Stream;
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+/// A boxed future, as returned by [`Transport::send`].
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// Sends HTTP requests on behalf of a [`Client`](crate::client::Client).
+///
+/// The request is complete: credentials, such as `Authorization`, and default
+/// headers have already been added. Implementations resolve as soon as the response headers
+/// arrive and stream the body through the returned [`Body`].
+///
+/// Errors are returned as they are, so implementations should use
+/// [`Error::Transport`] for their own failures. Failures created with
+/// [`TransportError::retryable`], such as refused connections or timeouts,
+/// are retried by the client's [`RetryPolicy`](crate::client::RetryPolicy)
+/// like [`Error::Reqwest`] connection errors.
+pub trait Transport: Send + Sync {
+    /// Sends a request and returns the response.
+    fn send(&self, request: http::Request<Bytes>) -> BoxFuture<'_, Result<http::Response<Body>>>;
+}
+
+impl<T: Transport + ?Sized> Transport for Arc<T> {
+    fn send(&self, request: http::Request<Bytes>) -> BoxFuture<'_, Result<http::Response<Body>>> {
+        (**self).send(request)
+    }
+}
+
+/// The body of an HTTP response, as a stream of byte chunks.
+pub struct Body {
+    inner: Pin<Box<dyn Stream<Item = Result<Bytes>> + Send>>,
+}
+
+impl Body {
+    /// An empty body.
+    pub fn empty() -> Self {
+        Self::from(Bytes::new())
+    }
+
+    /// Creates a body from a stream of byte chunks.
+    pub fn from_stream<S>(stream: S) -> Self
+    where
+        S: Stream<Item = Result<Bytes>> + Send + 'static,
+    {
+        Self {
+            inner: Box::pin(stream),
+        }
+    }
+
+    /// Puts `chunks` that were already read back in front of the body.
+    pub(crate) fn prepend(self, chunks: Vec<Bytes>) -> Self {
+        Self::from_stream(Prepend {
+            chunks: chunks.into(),
+            rest: self,
+        })
+    }
+
+    /// Reads the next chunk of the body.
+    pub(crate) async fn chunk(&mut self) -> Result<Option<Bytes>> {
+        std::future::poll_fn(|cx| self.inner.as_mut().poll_next(cx))
+            .await
+            .transpose()
+    }
+}
+
+impl std::fmt::Debug for Body {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Body").finish_non_exhaustive()
+    }
+}
+
+impl Stream for Body {
+    type Item = Result<Bytes>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.inner.as_mut().poll_next(cx)
+    }
+}
+
+impl From<Bytes> for Body {
+    fn from(bytes: Bytes) -> Self {
+        Self::from_stream(Once(Some(bytes).filter(|b| !b.is_empty())))
+    }
+}
+
+impl From<Vec<u8>> for Body {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self::from(Bytes::from(bytes))
+    }
+}
+
+impl From<String> for Body {
+    fn from(s: String) -> Self {
+        Self::from(Bytes::from(s))
+    }
+}
+
+impl From<&'static str> for Body {
+    fn from(s: &'static str) -> Self {
+        Self::from(Bytes::from_static(s.as_bytes()))
+    }
+}
+
+/// A stream that yields a single chunk.
+struct Once(Option<Bytes>);
+
+impl Stream for Once {
+    type Item = Result<Bytes>;
+
+    fn poll_next(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Poll::Ready(self.0.take().map(Ok))
+    }
+}
+
+/// A stream that yields some chunks before the rest of a body.
+struct Prepend {
+    chunks: std::collections::VecDeque<Bytes>,
+    rest: Body,
+}
+
+impl Stream for Prepend {
+    type Item = Result<Bytes>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        match self.chunks.pop_front() {
+            Some(chunk) => Poll::Ready(Some(Ok(chunk))),
+            None => Pin::new(&mut self.rest).poll_next(cx),
+        }
+    }
+}
+
+/// The default transport, backed by a [`reqwest::Client`].
+#[derive(Clone, Debug, Default)]
+pub struct ReqwestTransport {
+    client: reqwest::Client,
+}
+
+impl ReqwestTransport {
+    /// Sends requests through the given reqwest client.
+    pub fn new(client: reqwest::Client) -> Self {
+        Self { client }
+    }
+}
+
+impl Transport for ReqwestTransport {
+    fn send(&self, request: http::Request<Bytes>) -> BoxFuture<'_, Result<http::Response<Body>>> {
+        Box::pin(async move {
+            let timeout = request.extensions().get::<RequestTimeout>().copied();
+            let mut request = reqwest::Request::try_from(request)?;
+            if let Some(RequestTimeout(timeout)) = timeout {
+                *request.timeout_mut() = Some(timeout);
+            }
+            let resp = self.client.execute(request).await?;
+
+            let mut builder = http::Response::builder()
+                .status(resp.status())
+                .version(resp.version());
+            if let Some(headers) = builder.headers_mut() {
+                *headers = resp.headers().clone();
+            }
+            let body = Body::from_stream(ReqwestStream(Box::pin(resp.bytes_stream())));
+            builder
+                .body(body)
+                .map_err(|e| TransportError::new(e).into())
+        })
+    }
+}
+
+/// Adapts a reqwest byte stream to the client's error type.
+struct ReqwestStream(Pin<Box<dyn Stream<Item = reqwest::Result<Bytes>> + Send>>);
+
+impl Stream for ReqwestStream {
+    type Item = Result<Bytes>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.0
+            .as_mut()
+            .poll_next(cx)
+            .map(|item| item.map(|chunk| chunk.map_err(Error::from)))
+    }
+}
+
+type Handler = dyn Fn(&http::Request<Bytes>) -> Result<http::Response<Body>> + Send + Sync;
+
+/// A transport that answers requests in memory, without any network I/O.
+///
+/// Every request is passed to a handler function and recorded, so tests can
+/// inspect what the client sent via [`take_requests`](Self::take_requests).
+/// Share the transport through an [`Arc`] to keep access to it after handing
+/// it to [`ClientBuilder::transport`](crate::client::ClientBuilder::transport).
+pub struct InMemoryTransport {
+    handler: Box<Handler>,
+    requests: Mutex<Vec<http::Request<Bytes>>>,
+}
+
+impl InMemoryTransport {
+    /// Answers every request with the response returned by `handler`.
+    pub fn new<F>(handler: F) -> Self
+    where
+        F: Fn(&http::Request<Bytes>) -> Result<http::Response<Body>> + Send + Sync + 'static,
+    {
+        Self {
+            handler: Box::new(handler),
+            requests: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Answers every request with the same status code and JSON body.
+    pub fn json(status: http::StatusCode, body: serde_json::Value) -> Self {
+        let body = body.to_string();
+        Self::new(move |_| {
+            http::Response::builder()
+                .status(status)
+                .header(http::header::CONTENT_TYPE, "application/json")
+                .body(Body::from(body.clone()))
+                .map_err(|e| TransportError::new(e).into())
+        })
+    }
+
+    /// Returns the requests received so far, and forgets them.
+    pub fn take_requests(&self) -> Vec<http::Request<Bytes>> {
+        std::mem::take(&mut *self.requests.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+}
+
+impl std::fmt::Debug for InMemoryTransport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InMemoryTransport").finish_non_exhaustive()
+    }
+}
+
+impl Transport for InMemoryTransport {
+    fn send(&self, request: http::Request<Bytes>) -> BoxFuture<'_, Result<http::Response<Body>>> {
+        let response = (self.handler)(&request);
+        self.requests
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(request);
+        Box::pin(std::future::ready(response))
+    }
+}
+
+/// The transport a client was built with.
+#[derive(Clone)]
+pub(crate) struct SharedTransport(Arc<dyn Transport>);
+
+impl SharedTransport {
+    pub(crate) fn new(transport: impl Transport + 'static) -> Self {
+        Self(Arc::new(transport))
+    }
+
+    pub(crate) fn send(
+        &self,
+        request: http::Request<Bytes>,
+    ) -> BoxFuture<'_, Result<http::Response<Body>>> {
+        self.0.send(request)
+    }
+}
+
+impl std::fmt::Debug for SharedTransport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SharedTransport").finish_non_exhaustive()
+    }
+}
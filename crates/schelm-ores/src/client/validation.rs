@@ -0,0 +1,277 @@
+//! Client-side validation of request bodies.
+//!
+//! Mirrors the documented server-side constraints so that invalid requests fail
+//! fast with [`Error::Validation`], listing every offending field at once.
+
+use crate::client::endpoints::responses::ModelFallback;
+use crate::client::{Error, FieldError, Result};
+use crate::models;
+
+/// Maximum number of metadata key-value pairs.
+const MAX_METADATA_PAIRS: usize = 16;
+/// Maximum length of a metadata key, in characters.
+const MAX_METADATA_KEY_CHARS: usize = 64;
+/// Maximum length of a metadata value, in characters.
+const MAX_METADATA_VALUE_CHARS: usize = 512;
+/// Maximum value of `top_logprobs`.
+const MAX_TOP_LOGPROBS: i32 = 20;
+
+/// Collects field errors and turns them into a single [`Error::Validation`].
+#[derive(Debug, Default)]
+pub(crate) struct Validator {
+    errors: Vec<FieldError>,
+}
+
+impl Validator {
+    pub(crate) fn error(&mut self, field: impl Into<String>, message: impl Into<String>) {
+        self.errors.push(FieldError {
+            field: field.into(),
+            message: message.into(),
+        });
+    }
+
+    fn range(&mut self, field: &str, value: Option<f64>, min: f64, max: f64) {
+        if let Some(value) = value
+            && !(min..=max).contains(&value)
+        {
+            self.error(
+                field,
+                format!("must be between {min} and {max}, got {value}"),
+            );
+        }
+    }
+
+    fn positive(&mut self, field: &str, value: Option<i32>) {
+        if let Some(value) = value
+            && value <= 0
+        {
+            self.error(field, format!("must be positive, got {value}"));
+        }
+    }
+
+    fn metadata(&mut self, metadata: Option<&models::MetadataParam>) {
+        let Some(metadata) = metadata else {
+            return;
+        };
+        if metadata.len() > MAX_METADATA_PAIRS {
+            self.error(
+                "metadata",
+                format!(
+                    "must have at most {MAX_METADATA_PAIRS} pairs, got {}",
+                    metadata.len()
+                ),
+            );
+        }
+        let mut keys: Vec<&String> = metadata.keys().collect();
+        keys.sort();
+        for key in keys {
+            let key_chars = key.chars().count();
+            if key_chars > MAX_METADATA_KEY_CHARS {
+                self.error(
+                    format!("metadata.{key}"),
+                    format!(
+                        "key must be at most {MAX_METADATA_KEY_CHARS} characters, got {key_chars}"
+                    ),
+                );
+            }
+            let value_chars = metadata[key].chars().count();
+            if value_chars > MAX_METADATA_VALUE_CHARS {
+                self.error(
+                    format!("metadata.{key}"),
+                    format!(
+                        "value must be at most {MAX_METADATA_VALUE_CHARS} characters, got {value_chars}"
+                    ),
+                );
+            }
+        }
+    }
+
+    /// Reports extra body fields that are already set through a typed setter.
+    pub(crate) fn extra_body(
+        &mut self,
+        body: &models::CreateResponseBody,
+        extra: &serde_json::Map<String, serde_json::Value>,
+    ) {
+        if extra.is_empty() {
+            return;
+        }
+        let Ok(serde_json::Value::Object(typed)) = serde_json::to_value(body) else {
+            return;
+        };
+        for key in extra.keys() {
+            if typed.get(key).is_some_and(|value| !value.is_null()) {
+                self.error(format!("extra_body.{key}"), "conflicts with a typed field");
+            }
+        }
+    }
+
+    /// Reports invalid overrides in a model fallback chain.
+    pub(crate) fn fallbacks(&mut self, fallbacks: &[ModelFallback]) {
+        for (i, fallback) in fallbacks.iter().enumerate() {
+            if fallback.model.is_empty() {
+                self.error(format!("fallbacks[{i}].model"), "must not be empty");
+            }
+            self.positive(
+                &format!("fallbacks[{i}].max_output_tokens"),
+                fallback.max_output_tokens,
+            );
+        }
+    }
+
+    /// Reports extra headers that are invalid or set by the client itself.
+    pub(crate) fn extra_headers(&mut self, headers: &[(String, String)]) {
+        use reqwest::header::{ACCEPT, CONTENT_TYPE, HeaderName, HeaderValue};
+
+        for (name, value) in headers {
+            let field = format!("extra_headers.{name}");
+            match HeaderName::try_from(name.as_str()) {
+                Ok(name) if name == CONTENT_TYPE || name == ACCEPT => {
+                    self.error(field, "is set by the client");
+                }
+                Ok(_) if HeaderValue::from_str(value).is_err() => {
+                    self.error(field, "is not a valid header value");
+                }
+                Ok(_) => {}
+                Err(_) => self.error(field, "is not a valid header name"),
+            }
+        }
+    }
+
+    pub(crate) fn finish(self) -> Result<()> {
+        if self.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::Validation {
+                errors: self.errors,
+            })
+        }
+    }
+}
+
+/// Validates a `POST /responses` body.
+pub(crate) fn create_response(body: &models::CreateResponseBody) -> Validator {
+    let mut v = Validator::default();
+    v.range("temperature", body.temperature, 0.0, 2.0);
+    v.range("top_p", body.top_p, 0.0, 1.0);
+    v.range("presence_penalty", body.presence_penalty, -2.0, 2.0);
+    v.range("frequency_penalty", body.frequency_penalty, -2.0, 2.0);
+    v.positive("max_output_tokens", body.max_output_tokens);
+    v.positive("max_tool_calls", body.max_tool_calls);
+    if let Some(top_logprobs) = body.top_logprobs
+        && !(0..=MAX_TOP_LOGPROBS).contains(&top_logprobs)
+    {
+        v.error(
+            "top_logprobs",
+            format!("must be between 0 and {MAX_TOP_LOGPROBS}, got {top_logprobs}"),
+        );
+    }
+    v.metadata(body.metadata.as_ref());
+    v
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn body() -> models::CreateResponseBody {
+        serde_json::from_value(serde_json::json!({ "model": "gpt-test", "input": "hi" })).unwrap()
+    }
+
+    fn fields(result: Result<()>) -> Vec<String> {
+        match result {
+            Err(Error::Validation { errors }) => errors.into_iter().map(|e| e.field).collect(),
+            other => panic!("expected Validation error, got: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn accepts_values_at_the_bounds() {
+        let mut body = body();
+        body.temperature = Some(2.0);
+        body.top_p = Some(0.0);
+        body.presence_penalty = Some(-2.0);
+        body.top_logprobs = Some(20);
+        body.max_output_tokens = Some(1);
+        assert!(create_response(&body).finish().is_ok());
+    }
+
+    #[test]
+    fn reports_every_failing_field() {
+        let mut body = body();
+        body.temperature = Some(2.5);
+        body.top_p = Some(f64::NAN);
+        body.frequency_penalty = Some(-3.0);
+        body.max_output_tokens = Some(0);
+        body.max_tool_calls = Some(-1);
+        body.top_logprobs = Some(21);
+        assert_eq!(
+            fields(create_response(&body).finish()),
+            [
+                "temperature",
+                "top_p",
+                "frequency_penalty",
+                "max_output_tokens",
+                "max_tool_calls",
+                "top_logprobs"
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_oversized_metadata() {
+        let mut body = body();
+        let mut metadata: models::MetadataParam = (0..17)
+            .map(|i| (format!("key{i:02}"), "v".to_owned()))
+            .collect();
+        metadata.insert("k".repeat(65), "v".to_owned());
+        metadata.insert("long".to_owned(), "v".repeat(513));
+        body.metadata = Some(metadata);
+
+        let fields = fields(create_response(&body).finish());
+        assert_eq!(fields[0], "metadata");
+        assert!(fields.contains(&format!("metadata.{}", "k".repeat(65))));
+        assert!(fields.contains(&"metadata.long".to_owned()));
+        assert_eq!(fields.len(), 3);
+    }
+
+    #[test]
+    fn rejects_invalid_fallbacks() {
+        let mut v = Validator::default();
+        v.fallbacks(&[
+            ModelFallback::new("gpt-mini"),
+            ModelFallback::new("").max_output_tokens(0),
+        ]);
+        assert_eq!(
+            fields(v.finish()),
+            ["fallbacks[1].model", "fallbacks[1].max_output_tokens"]
+        );
+    }
+
+    #[test]
+    fn rejects_extras_that_conflict() {
+        let mut body = body();
+        body.temperature = Some(1.0);
+        let extra = serde_json::json!({ "temperature": 0.5, "top_k": 40, "top_p": 0.9 });
+        let serde_json::Value::Object(extra) = extra else {
+            unreachable!()
+        };
+
+        let mut v = Validator::default();
+        v.extra_body(&body, &extra);
+        v.extra_headers(&[
+            ("x-vendor-flag".to_owned(), "on".to_owned()),
+            ("Content-Type".to_owned(), "text/plain".to_owned()),
+            ("bad header".to_owned(), "v".to_owned()),
+            ("x-newline".to_owned(), "a\nb".to_owned()),
+        ]);
+        assert_eq!(
+            fields(v.finish()),
+            [
+                "extra_body.temperature",
+                "extra_headers.Content-Type",
+                "extra_headers.bad header",
+                "extra_headers.x-newline"
+            ]
+        );
+    }
+}
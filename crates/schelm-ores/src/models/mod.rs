@@ -71,6 +71,24 @@ pub enum IncludeEnum {
     MessageOutputTextLogprobs,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum FilePurposeEnum {
+    /// Files used as model inputs.
+    UserData,
+    /// Images used for vision inputs.
+    Vision,
+    /// Files used by assistants.
+    Assistants,
+    /// Input files for the batch API.
+    Batch,
+    /// Training files for fine-tuning.
+    #[serde(rename = "fine-tune")]
+    FineTune,
+    /// Files used for evals data sources.
+    Evals,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum ToolChoiceValueEnum {
@@ -183,6 +201,8 @@ pub struct InputTextContentParam {
 pub struct InputImageContentParamAutoParam {
     /// The URL of the image to be sent to the model. A fully qualified URL or base64 encoded image in a data URL.
     pub image_url: Option<String>,
+    /// The ID of an uploaded file to be sent to the model.
+    pub file_id: Option<String>,
     /// The detail level of the image to be sent to the model. One of `high`, `low`, or `auto`. Defaults to `auto`.
     pub detail: Option<ImageDetail>,
 }
@@ -197,6 +217,8 @@ pub struct InputFileContentParam {
     pub file_data: Option<String>,
     /// The URL of the file to be sent to the model.
     pub file_url: Option<String>,
+    /// The ID of an uploaded file to be sent to the model.
+    pub file_id: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
@@ -488,6 +510,8 @@ pub struct CreateResponseBody {
     pub input: Option<CreateResponseInput>,
     /// The ID of the response to use as the prior turn for this request.
     pub previous_response_id: Option<String>,
+    /// The conversation this response belongs to. Items from the conversation are prepended to the input, and the input and output of this response are added to it.
+    pub conversation: Option<ConversationParam>,
     pub include: Option<Vec<IncludeEnum>>,
     /// A list of tools that the model may call while generating the response.
     pub tools: Option<Vec<ResponsesToolParam>>,
@@ -535,6 +559,81 @@ pub struct CreateResponseBody {
     pub top_logprobs: Option<i32>,
 }
 
+/// The conversation a response belongs to.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum ConversationParam {
+    /// The ID of the conversation.
+    Id(String),
+    /// An object referencing the conversation by ID.
+    Object(ConversationReference),
+}
+
+/// A reference to a conversation by ID.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ConversationReference {
+    /// The ID of the conversation.
+    pub id: String,
+}
+
+/// The subset of [`CreateResponseBody`] accepted by the input token counting endpoint.
+///
+/// Built from a [`CreateResponseBody`] so that the counted input matches the
+/// request that would be sent.
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct InputTokensBody {
+    /// The model to use for this request, e.g. 'gpt-5.2'.
+    pub model: Option<String>,
+    /// Context to provide to the model for the scope of this request.
+    pub input: Option<CreateResponseInput>,
+    /// The ID of the response to use as the prior turn for this request.
+    pub previous_response_id: Option<String>,
+    /// The conversation this request belongs to.
+    pub conversation: Option<ConversationParam>,
+    /// A list of tools that the model may call while generating the response.
+    pub tools: Option<Vec<ResponsesToolParam>>,
+    /// Controls which tool the model should use, if any.
+    pub tool_choice: Option<ToolChoiceParam>,
+    /// Configuration options for text output.
+    pub text: Option<TextParam>,
+    /// Configuration options for reasoning behavior.
+    pub reasoning: Option<ReasoningParam>,
+    /// Whether the model may call multiple tools in parallel.
+    pub parallel_tool_calls: Option<bool>,
+    /// Controls how the service truncates the input when it exceeds the model context window.
+    pub truncation: Option<TruncationEnum>,
+    /// Additional instructions to guide the model for this request.
+    pub instructions: Option<String>,
+}
+
+impl From<&CreateResponseBody> for InputTokensBody {
+    fn from(body: &CreateResponseBody) -> Self {
+        Self {
+            model: body.model.clone(),
+            input: body.input.clone(),
+            previous_response_id: body.previous_response_id.clone(),
+            conversation: body.conversation.clone(),
+            tools: body.tools.clone(),
+            tool_choice: body.tool_choice.clone(),
+            text: body.text.clone(),
+            reasoning: body.reasoning.clone(),
+            parallel_tool_calls: body.parallel_tool_calls,
+            truncation: body.truncation.clone(),
+            instructions: body.instructions.clone(),
+        }
+    }
+}
+
+/// The input token count that was computed for a request.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct InputTokensResource {
+    /// The object type, which was always `response.input_tokens`.
+    pub object: String,
+    /// The number of input tokens the request would consume.
+    pub input_tokens: i32,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(untagged)]
 pub enum CreateResponseInput {
@@ -869,6 +968,86 @@ pub struct ResponseResource {
     pub prompt_cache_key: Option<String>,
 }
 
+/// A conversation that stores items across responses.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ConversationResource {
+    /// The unique ID of the conversation.
+    pub id: String,
+    /// The object type, which was always `conversation`.
+    pub object: String,
+    /// The Unix timestamp (in seconds) for when the conversation was created.
+    pub created_at: i64,
+    /// Developer-defined metadata that was associated with the conversation.
+    pub metadata: serde_json::Value,
+}
+
+/// The request body for creating a conversation.
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct CreateConversationBody {
+    /// Initial items to include in the conversation context. Up to 20 items.
+    pub items: Option<Vec<ItemParam>>,
+    /// Set of 16 key-value pairs that can be attached to an object.
+    pub metadata: Option<MetadataParam>,
+}
+
+/// The request body for updating a conversation.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct UpdateConversationBody {
+    /// Set of 16 key-value pairs that can be attached to an object.
+    pub metadata: MetadataParam,
+}
+
+/// The request body for adding items to a conversation.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AddConversationItemsBody {
+    /// The items to add to the conversation. Up to 20 items at a time.
+    pub items: Vec<ItemParam>,
+}
+
+/// A model that was offered by the server.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Model {
+    /// The model identifier, which can be referenced in API requests.
+    pub id: String,
+    /// The object type, which was always `model`.
+    pub object: String,
+    /// The Unix timestamp (in seconds) for when the model was created, if reported.
+    pub created: Option<i64>,
+    /// The organization that owns the model, if reported.
+    pub owned_by: Option<String>,
+}
+
+/// A file that was uploaded to the server.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct FileResource {
+    /// The file identifier, which can be referenced in API requests.
+    pub id: String,
+    /// The object type, which was always `file`.
+    pub object: String,
+    /// The size of the file, in bytes.
+    pub bytes: i64,
+    /// The Unix timestamp (in seconds) for when the file was created.
+    pub created_at: i64,
+    /// The Unix timestamp (in seconds) for when the file will expire, if it expires.
+    pub expires_at: Option<i64>,
+    /// The name of the file.
+    pub filename: String,
+    /// The intended purpose of the file.
+    pub purpose: String,
+}
+
+/// Confirmation that was returned after deleting an object.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct DeletedResource {
+    /// The ID of the object that was deleted.
+    pub id: String,
+    /// The type of the object that was deleted, e.g. `response`.
+    pub object: String,
+    /// Whether the object was deleted.
+    pub deleted: bool,
+}
+
 #[derive(Debug, Serialize, Clone, PartialEq)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum StreamingEvent {
@@ -1703,6 +1882,7 @@ mod tests {
             model: Some("test".into()),
             input: Some(CreateResponseInput::String("hi".into())),
             previous_response_id: None,
+            conversation: None,
             include: None,
             tools: None,
             tool_choice: None,
@@ -0,0 +1,169 @@
+#![cfg(feature = "blocking")]
+
+pub mod common;
+
+use std::sync::Arc;
+
+use bytes::Bytes;
+use wiremock::matchers::{body_partial_json, method, path};
+use wiremock::{Mock, ResponseTemplate};
+
+use schelm_ores::client::blocking;
+use schelm_ores::client::{Body, Client, Error, InMemoryTransport};
+use schelm_ores::models::StreamingEvent;
+
+fn base_url() -> url::Url {
+    url::Url::parse("http://memory.invalid/v1").unwrap()
+}
+
+fn delta_frame(seq: i32, delta: &str) -> String {
+    let event = serde_json::json!({
+        "type": "response.output_text.delta",
+        "sequence_number": seq,
+        "item_id": "msg_001",
+        "output_index": 0,
+        "content_index": 0,
+        "delta": delta,
+        "logprobs": []
+    });
+    format!("event: response.output_text.delta\ndata: {event}\n\n")
+}
+
+#[test]
+fn blocking_send_returns_response() {
+    let transport = Arc::new(InMemoryTransport::json(
+        http::StatusCode::OK,
+        common::success_response_body(),
+    ));
+    let client = Client::builder("test-api-key", base_url())
+        .transport(transport.clone())
+        .build_blocking()
+        .unwrap();
+
+    let resp = client
+        .responses()
+        .create_text("gpt-test", "hello")
+        .instructions("be brief")
+        .temperature(0.5)
+        .send()
+        .expect("request should succeed");
+    assert_eq!(resp.id, "resp_test_123");
+
+    let requests = transport.take_requests();
+    assert_eq!(requests.len(), 1);
+    let body: serde_json::Value = serde_json::from_slice(requests[0].body()).unwrap();
+    assert_eq!(body["input"], "hello");
+    assert_eq!(body["instructions"], "be brief");
+    assert_eq!(body["temperature"], 0.5);
+}
+
+#[test]
+fn blocking_send_stream_iterates_events() {
+    let sse = format!(
+        "{}{}data: [DONE]\n\n",
+        delta_frame(0, "Hello"),
+        delta_frame(1, " world")
+    );
+    let transport = InMemoryTransport::new(move |_| {
+        Ok(http::Response::builder()
+            .header("content-type", "text/event-stream")
+            .body(Body::from(Bytes::from(sse.clone())))
+            .unwrap())
+    });
+    let client = Client::builder("test-api-key", base_url())
+        .transport(transport)
+        .build_blocking()
+        .unwrap();
+
+    let deltas: Vec<String> = client
+        .responses()
+        .create_text("gpt-test", "hello")
+        .send_stream()
+        .expect("send_stream should succeed")
+        .map(|event| event.expect("event should decode"))
+        .filter_map(|event| match event {
+            StreamingEvent::ResponseOutputTextDelta { delta, .. } => Some(delta),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(deltas, ["Hello", " world"]);
+}
+
+#[test]
+fn blocking_send_returns_api_errors() {
+    let transport = InMemoryTransport::json(
+        http::StatusCode::BAD_REQUEST,
+        serde_json::json!({
+            "error": { "message": "bad input", "type": "invalid_request_error" }
+        }),
+    );
+    let client = blocking::Client::new(
+        Client::builder("test-api-key", base_url())
+            .transport(transport)
+            .build()
+            .unwrap(),
+    )
+    .unwrap();
+
+    let err = client
+        .responses()
+        .create_text("gpt-test", "hello")
+        .send()
+        .expect_err("request should fail");
+    assert!(
+        matches!(&err, Error::Api(e) if e.status == 400 && e.message.as_deref() == Some("bad input")),
+        "expected Api error, got: {err:?}"
+    );
+}
+
+#[test]
+fn blocking_validation_runs_before_sending() {
+    let transport = Arc::new(InMemoryTransport::json(
+        http::StatusCode::OK,
+        common::success_response_body(),
+    ));
+    let client = Client::builder("test-api-key", base_url())
+        .transport(transport.clone())
+        .build_blocking()
+        .unwrap();
+
+    let err = client
+        .responses()
+        .create_text("gpt-test", "hello")
+        .temperature(3.0)
+        .send()
+        .expect_err("validation should fail");
+    assert!(matches!(err, Error::Validation { .. }), "got: {err:?}");
+    assert!(transport.take_requests().is_empty());
+}
+
+#[test]
+fn blocking_client_talks_to_server() {
+    // The mock server runs on its own runtime, the blocking client outside of it.
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let server = runtime.block_on(async {
+        let server = common::mock_server().await;
+        Mock::given(method("POST"))
+            .and(path("/responses"))
+            .and(body_partial_json(
+                serde_json::json!({ "model": "gpt-test" }),
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(common::success_response_body()))
+            .expect(1)
+            .mount(&server)
+            .await;
+        server
+    });
+
+    let client = common::test_client_builder(&server)
+        .build_blocking()
+        .unwrap();
+    let resp = client
+        .responses()
+        .create_text("gpt-test", "hello")
+        .send()
+        .expect("request should succeed");
+    assert_eq!(resp.id, "resp_test_123");
+
+    runtime.block_on(server.verify());
+}
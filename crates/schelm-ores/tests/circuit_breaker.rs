@@ -0,0 +1,148 @@
+pub mod common;
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use wiremock::matchers::{body_partial_json, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use schelm_ores::client::{
+    CircuitBreaker, CircuitState, Client, Error, InMemoryTransport, TransportError,
+};
+
+async fn send(client: &Client, model: &str) -> schelm_ores::client::Result<()> {
+    client
+        .responses()
+        .create_text(model, "hello")
+        .send()
+        .await
+        .map(|_| ())
+}
+
+async fn mount(server: &MockServer, model: &str, status: u16, expected: u64) {
+    Mock::given(method("POST"))
+        .and(path("/responses"))
+        .and(body_partial_json(serde_json::json!({ "model": model })))
+        .respond_with(ResponseTemplate::new(status).set_body_json(common::success_response_body()))
+        .up_to_n_times(expected)
+        .expect(expected)
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn open_circuit_fails_fast_and_recovers() {
+    let server = common::mock_server().await;
+    mount(&server, "gpt-test", 503, 2).await;
+
+    let changes = Arc::new(Mutex::new(Vec::new()));
+    let recorder = changes.clone();
+    let breaker = CircuitBreaker::new()
+        .failure_threshold(2)
+        .open_duration(Duration::from_millis(100))
+        .on_state_change(move |change| {
+            recorder
+                .lock()
+                .unwrap()
+                .push((change.model.clone(), change.to));
+        });
+    let client = common::test_client_builder(&server)
+        .circuit_breaker(breaker.clone())
+        .build()
+        .unwrap();
+    let base_url = url::Url::parse(&server.uri()).unwrap();
+
+    for _ in 0..2 {
+        let err = send(&client, "gpt-test").await.expect_err("server fails");
+        assert_eq!(err.api_error().unwrap().status.as_u16(), 503);
+    }
+    assert_eq!(
+        breaker.state(&base_url, Some("gpt-test")),
+        CircuitState::Open
+    );
+
+    let err = send(&client, "gpt-test")
+        .await
+        .expect_err("circuit is open");
+    match &err {
+        Error::CircuitOpen { model, .. } => assert_eq!(model.as_deref(), Some("gpt-test")),
+        other => panic!("expected CircuitOpen, got: {other:?}"),
+    }
+    assert!(err.to_string().contains("(model gpt-test)"), "{err}");
+
+    tokio::time::sleep(Duration::from_millis(150)).await;
+    mount(&server, "gpt-test", 200, 1).await;
+    send(&client, "gpt-test")
+        .await
+        .expect("probe should succeed");
+    assert_eq!(
+        breaker.state(&base_url, Some("gpt-test")),
+        CircuitState::Closed
+    );
+
+    let model = Some("gpt-test".to_owned());
+    assert_eq!(
+        *changes.lock().unwrap(),
+        [
+            (model.clone(), CircuitState::Open),
+            (model.clone(), CircuitState::HalfOpen),
+            (model, CircuitState::Closed),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn circuits_are_kept_per_model() {
+    let server = common::mock_server().await;
+    mount(&server, "gpt-large", 500, 1).await;
+    mount(&server, "gpt-small", 200, 2).await;
+
+    let client = common::test_client_builder(&server)
+        .circuit_breaker(CircuitBreaker::new().failure_threshold(1))
+        .build()
+        .unwrap();
+
+    send(&client, "gpt-large").await.expect_err("server fails");
+    send(&client, "gpt-small")
+        .await
+        .expect("other models are unaffected");
+
+    // Open circuits count as capacity errors for fallback chains.
+    client
+        .responses()
+        .create_text("gpt-large", "hello")
+        .fallback("gpt-small")
+        .send()
+        .await
+        .expect("fallback model should answer");
+}
+
+#[tokio::test]
+async fn transport_errors_open_the_circuit() {
+    let transport = Arc::new(InMemoryTransport::new(|_| {
+        Err(TransportError::retryable("connection reset").into())
+    }));
+    let breaker = CircuitBreaker::new().failure_threshold(2);
+    let base_url = url::Url::parse("http://localhost:8080/v1").unwrap();
+    let client = Client::builder("key", base_url.clone())
+        .transport(transport.clone())
+        .circuit_breaker(breaker.clone())
+        .build()
+        .unwrap();
+
+    for _ in 0..2 {
+        let err = send(&client, "gpt-test")
+            .await
+            .expect_err("transport fails");
+        assert!(matches!(err, Error::Transport(_)), "got: {err:?}");
+    }
+    assert_eq!(
+        breaker.state(&base_url, Some("gpt-test")),
+        CircuitState::Open
+    );
+    let err = send(&client, "gpt-test")
+        .await
+        .expect_err("circuit is open");
+    assert!(matches!(err, Error::CircuitOpen { .. }), "got: {err:?}");
+    assert_eq!(transport.take_requests().len(), 2);
+}
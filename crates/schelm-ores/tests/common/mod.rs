@@ -12,12 +12,18 @@ pub async fn mock_server() -> MockServer {
 /// Builds an [`ores::client::Client`] whose base URL points at the given mock
 /// server so that all HTTP traffic stays local.
 pub fn test_client(server: &MockServer) -> schelm_ores::client::Client {
-    let base_url = url::Url::parse(&server.uri()).expect("mock server URI should be a valid URL");
-    schelm_ores::client::Client::builder("test-api-key", base_url)
+    test_client_builder(server)
         .build()
         .expect("client builder should not fail with valid inputs")
 }
 
+/// Returns a [`ores::client::ClientBuilder`] pointing at the given mock server,
+/// for tests that need to customize the client before building it.
+pub fn test_client_builder(server: &MockServer) -> schelm_ores::client::ClientBuilder {
+    let base_url = url::Url::parse(&server.uri()).expect("mock server URI should be a valid URL");
+    schelm_ores::client::Client::builder("test-api-key", base_url)
+}
+
 /// Returns a minimal, valid JSON body representing a successful
 /// `ResponseResource` from the API.
 ///
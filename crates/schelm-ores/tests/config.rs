@@ -0,0 +1,53 @@
+#![cfg(feature = "profiles")]
+
+pub mod common;
+
+use wiremock::matchers::{bearer_token, method, path};
+use wiremock::{Mock, ResponseTemplate};
+
+use schelm_ores::client::Profiles;
+
+#[tokio::test]
+async fn client_from_profile_file() {
+    let server = common::mock_server().await;
+
+    Mock::given(method("POST"))
+        .and(path("/staging/responses"))
+        .and(bearer_token("staging-key"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(common::success_response_body()))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let file =
+        std::env::temp_dir().join(format!("schelm-ores-profiles-{}.toml", std::process::id()));
+    std::fs::write(
+        &file,
+        format!(
+            r#"
+            default = "local"
+
+            [profiles.local]
+            base_url = "http://localhost:1/v1"
+            api_key = "local-key"
+
+            [profiles.staging]
+            base_url = "{}/staging"
+            api_key = "staging-key"
+            "#,
+            server.uri()
+        ),
+    )
+    .unwrap();
+
+    let profiles = Profiles::load(&file).expect("profiles should load");
+    std::fs::remove_file(&file).unwrap();
+
+    let client = profiles.builder("staging").unwrap().build().unwrap();
+    client
+        .responses()
+        .create_text("gpt-test", "hello")
+        .send()
+        .await
+        .expect("request should succeed");
+}
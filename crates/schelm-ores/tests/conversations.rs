@@ -0,0 +1,234 @@
+pub mod common;
+
+use wiremock::matchers::{body_json, body_partial_json, method, path, query_param};
+use wiremock::{Mock, ResponseTemplate};
+
+use schelm_ores::models::{
+    CreateResponseInput, ItemField, ItemParam, MessageItemParam, UserMessageContent,
+    UserMessageItemParam,
+};
+
+fn conversation_body(metadata: serde_json::Value) -> serde_json::Value {
+    serde_json::json!({
+        "id": "conv_test_123",
+        "object": "conversation",
+        "created_at": 1700000000,
+        "metadata": metadata
+    })
+}
+
+fn user_item(text: &str) -> ItemParam {
+    ItemParam::Message(MessageItemParam::User(UserMessageItemParam {
+        id: None,
+        role: "user".into(),
+        content: UserMessageContent::String(text.into()),
+        status: None,
+    }))
+}
+
+#[tokio::test]
+async fn create_conversation_with_items_and_metadata() {
+    let server = common::mock_server().await;
+
+    Mock::given(method("POST"))
+        .and(path("/conversations"))
+        .and(body_json(serde_json::json!({
+            "items": [{ "type": "message", "role": "user", "content": "Hi" }],
+            "metadata": { "topic": "demo" }
+        })))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(conversation_body(serde_json::json!({ "topic": "demo" }))),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::test_client(&server);
+    let conversation = client
+        .conversations()
+        .create()
+        .items(vec![user_item("Hi")])
+        .metadata([("topic".to_owned(), "demo".to_owned())].into())
+        .send()
+        .await
+        .expect("request should succeed");
+
+    assert_eq!(conversation.id, "conv_test_123");
+    assert_eq!(conversation.object, "conversation");
+    assert_eq!(conversation.metadata["topic"], "demo");
+}
+
+#[tokio::test]
+async fn retrieve_update_and_delete_conversation() {
+    let server = common::mock_server().await;
+
+    Mock::given(method("GET"))
+        .and(path("/conversations/conv_test_123"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(conversation_body(serde_json::json!({}))),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/conversations/conv_test_123"))
+        .and(body_json(
+            serde_json::json!({ "metadata": { "owner": "ops" } }),
+        ))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(conversation_body(serde_json::json!({ "owner": "ops" }))),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/conversations/conv_test_123"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "conv_test_123",
+            "object": "conversation.deleted",
+            "deleted": true
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::test_client(&server);
+    let conversations = client.conversations();
+
+    let retrieved = conversations
+        .retrieve("conv_test_123")
+        .await
+        .expect("retrieve should succeed");
+    assert_eq!(retrieved.id, "conv_test_123");
+
+    let updated = conversations
+        .update(
+            "conv_test_123",
+            [("owner".to_owned(), "ops".to_owned())].into(),
+        )
+        .await
+        .expect("update should succeed");
+    assert_eq!(updated.metadata["owner"], "ops");
+
+    let deleted = conversations
+        .delete("conv_test_123")
+        .await
+        .expect("delete should succeed");
+    assert!(deleted.deleted);
+    assert_eq!(deleted.object, "conversation.deleted");
+}
+
+#[tokio::test]
+async fn retrieve_missing_conversation_returns_not_found_error() {
+    let server = common::mock_server().await;
+
+    Mock::given(method("GET"))
+        .and(path("/conversations/conv_missing"))
+        .respond_with(ResponseTemplate::new(404))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::test_client(&server);
+    let err = client
+        .conversations()
+        .retrieve("conv_missing")
+        .await
+        .expect_err("should return an error for 404");
+
+    assert!(
+        matches!(err, schelm_ores::client::Error::NotFound(_)),
+        "expected NotFound error, got: {err:?}"
+    );
+}
+
+#[tokio::test]
+async fn list_add_and_delete_conversation_items() {
+    let server = common::mock_server().await;
+    let item = serde_json::json!({
+        "type": "message",
+        "id": "msg_1",
+        "status": "completed",
+        "role": "user",
+        "content": [{ "type": "input_text", "text": "Hi" }]
+    });
+    let list = serde_json::json!({
+        "object": "list",
+        "data": [item],
+        "first_id": "msg_1",
+        "last_id": "msg_1",
+        "has_more": false
+    });
+
+    Mock::given(method("GET"))
+        .and(path("/conversations/conv_test_123/items"))
+        .and(query_param("limit", "10"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(list.clone()))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/conversations/conv_test_123/items"))
+        .and(body_partial_json(serde_json::json!({
+            "items": [{ "type": "message", "role": "user", "content": "Hi" }]
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(list))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/conversations/conv_test_123/items/msg_1"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(conversation_body(serde_json::json!({}))),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::test_client(&server);
+    let items = client.conversations().items("conv_test_123");
+
+    let page = items
+        .list()
+        .limit(10)
+        .send()
+        .await
+        .expect("list should succeed");
+    assert_eq!(page.data.len(), 1);
+    assert!(matches!(page.data[0], ItemField::Message(_)));
+
+    let added = items
+        .add(vec![user_item("Hi")])
+        .await
+        .expect("add should succeed");
+    assert_eq!(added.first_id.as_deref(), Some("msg_1"));
+
+    let conversation = items.delete("msg_1").await.expect("delete should succeed");
+    assert_eq!(conversation.id, "conv_test_123");
+}
+
+#[tokio::test]
+async fn create_response_sends_conversation() {
+    let server = common::mock_server().await;
+
+    Mock::given(method("POST"))
+        .and(path("/responses"))
+        .and(body_partial_json(serde_json::json!({
+            "conversation": "conv_test_123"
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(common::success_response_body()))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::test_client(&server);
+    client
+        .responses()
+        .create("gpt-test", CreateResponseInput::String("Continue".into()))
+        .conversation("conv_test_123")
+        .send()
+        .await
+        .expect("request should succeed");
+}
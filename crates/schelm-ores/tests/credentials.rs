@@ -0,0 +1,131 @@
+pub mod common;
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use schelm_ores::client::{
+    BoxFuture, Client, Credential, CredentialProvider, Error, InMemoryTransport, Result,
+    RetryPolicy, StaticCredentials,
+};
+
+fn base_url() -> url::Url {
+    url::Url::parse("http://memory.invalid/v1").unwrap()
+}
+
+fn ok_transport() -> Arc<InMemoryTransport> {
+    Arc::new(InMemoryTransport::json(
+        http::StatusCode::OK,
+        common::success_response_body(),
+    ))
+}
+
+/// Hands out a new key for every request.
+struct RotatingKeys(AtomicUsize);
+
+impl CredentialProvider for RotatingKeys {
+    fn credential(&self) -> BoxFuture<'_, Result<Credential>> {
+        let n = self.0.fetch_add(1, Ordering::SeqCst);
+        Box::pin(async move { Credential::bearer(format!("key-{n}")) })
+    }
+}
+
+async fn send(client: &Client) -> Result<()> {
+    client
+        .responses()
+        .create_text("gpt-test", "hello")
+        .send()
+        .await
+        .map(|_| ())
+}
+
+#[tokio::test]
+async fn provider_is_consulted_for_every_request() {
+    let transport = ok_transport();
+    let client = Client::builder("unused", base_url())
+        .transport(transport.clone())
+        .credentials(RotatingKeys(AtomicUsize::new(0)))
+        .build()
+        .unwrap();
+
+    send(&client).await.unwrap();
+    send(&client).await.unwrap();
+
+    let keys: Vec<_> = transport
+        .take_requests()
+        .iter()
+        .map(|r| r.headers()["authorization"].clone())
+        .collect();
+    assert_eq!(keys, ["Bearer key-0", "Bearer key-1"]);
+}
+
+#[tokio::test]
+async fn provider_is_consulted_again_on_retry() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = calls.clone();
+    let transport = Arc::new(InMemoryTransport::new(move |_| {
+        let status = match counter.fetch_add(1, Ordering::SeqCst) {
+            0 => http::StatusCode::SERVICE_UNAVAILABLE,
+            _ => http::StatusCode::OK,
+        };
+        Ok(http::Response::builder()
+            .status(status)
+            .body(common::success_response_body().to_string().into())
+            .unwrap())
+    }));
+    let client = Client::builder("unused", base_url())
+        .transport(transport.clone())
+        .credentials(RotatingKeys(AtomicUsize::new(0)))
+        .retry_policy(RetryPolicy::default().base_delay(Duration::from_millis(1)))
+        .build()
+        .unwrap();
+
+    send(&client).await.unwrap();
+
+    let requests = transport.take_requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[1].headers()["authorization"], "Bearer key-1");
+}
+
+#[tokio::test]
+async fn api_key_header_scheme() {
+    let transport = ok_transport();
+    let client = Client::builder("unused", base_url())
+        .transport(transport.clone())
+        .credentials(StaticCredentials::new(
+            Credential::api_key("azure-key").unwrap(),
+        ))
+        .build()
+        .unwrap();
+
+    send(&client).await.unwrap();
+
+    let request = &transport.take_requests()[0];
+    assert_eq!(request.headers()["api-key"], "azure-key");
+    assert!(request.headers().get("authorization").is_none());
+}
+
+#[tokio::test]
+async fn provider_errors_abort_the_request() {
+    struct Failing;
+
+    impl CredentialProvider for Failing {
+        fn credential(&self) -> BoxFuture<'_, Result<Credential>> {
+            Box::pin(async { Err(Error::Credentials("token endpoint unavailable".into())) })
+        }
+    }
+
+    let transport = ok_transport();
+    let client = Client::builder("unused", base_url())
+        .transport(transport.clone())
+        .credentials(Failing)
+        .build()
+        .unwrap();
+
+    let err = send(&client).await.unwrap_err();
+    assert!(
+        matches!(&err, Error::Credentials(e) if e.to_string() == "token endpoint unavailable"),
+        "got: {err:?}"
+    );
+    assert!(transport.take_requests().is_empty());
+}
@@ -0,0 +1,157 @@
+pub mod common;
+
+use wiremock::matchers::{header_regex, method, path, query_param};
+use wiremock::{Mock, ResponseTemplate};
+
+use schelm_ores::models::{
+    FilePurposeEnum, InputFileContentParam, ItemParam, MessageItemParam, UserMessageContent,
+    UserMessageContentPart, UserMessageItemParam,
+};
+
+fn file_body() -> serde_json::Value {
+    serde_json::json!({
+        "id": "file_test_123",
+        "object": "file",
+        "bytes": 5,
+        "created_at": 1700000000,
+        "expires_at": null,
+        "filename": "report.pdf",
+        "purpose": "user_data"
+    })
+}
+
+#[tokio::test]
+async fn upload_file_sends_multipart_form() {
+    let server = common::mock_server().await;
+
+    Mock::given(method("POST"))
+        .and(path("/files"))
+        .and(header_regex(
+            "content-type",
+            "^multipart/form-data; boundary=.+$",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(file_body()))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::test_client(&server);
+    let file = client
+        .files()
+        .upload("report.pdf", b"%PDF-".to_vec(), FilePurposeEnum::UserData)
+        .content_type("application/pdf")
+        .send()
+        .await
+        .expect("request should succeed");
+
+    assert_eq!(file.id, "file_test_123");
+    assert_eq!(file.purpose, "user_data");
+
+    let received = server.received_requests().await.unwrap();
+    let body = String::from_utf8(received[0].body.clone()).unwrap();
+    assert!(
+        body.contains("name=\"purpose\"\r\n\r\nuser_data\r\n"),
+        "body was: {body}"
+    );
+    assert!(
+        body.contains("name=\"file\"; filename=\"report.pdf\"\r\nContent-Type: application/pdf\r\n\r\n%PDF-\r\n"),
+        "body was: {body}"
+    );
+}
+
+#[tokio::test]
+async fn list_retrieve_download_and_delete_files() {
+    let server = common::mock_server().await;
+
+    Mock::given(method("GET"))
+        .and(path("/files"))
+        .and(query_param("purpose", "user_data"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "object": "list",
+            "data": [file_body()],
+            "first_id": "file_test_123",
+            "last_id": "file_test_123",
+            "has_more": false
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/files/file_test_123"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(file_body()))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/files/file_test_123/content"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(b"%PDF-".to_vec()))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/files/file_test_123"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "file_test_123",
+            "object": "file",
+            "deleted": true
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::test_client(&server);
+    let files = client.files();
+
+    let page = files
+        .list()
+        .purpose(FilePurposeEnum::UserData)
+        .send()
+        .await
+        .expect("list should succeed");
+    assert_eq!(page.data.len(), 1);
+    assert_eq!(page.data[0].filename, "report.pdf");
+
+    let file = files
+        .retrieve("file_test_123")
+        .await
+        .expect("retrieve should succeed");
+    assert_eq!(file.bytes, 5);
+
+    let content = files
+        .content("file_test_123")
+        .await
+        .expect("download should succeed");
+    assert_eq!(&content[..], b"%PDF-");
+
+    let deleted = files
+        .delete("file_test_123")
+        .await
+        .expect("delete should succeed");
+    assert!(deleted.deleted);
+}
+
+#[test]
+fn file_input_serializes_file_id() {
+    let item = ItemParam::Message(MessageItemParam::User(UserMessageItemParam {
+        id: None,
+        role: "user".into(),
+        content: UserMessageContent::Array(vec![UserMessageContentPart::InputFile(
+            InputFileContentParam {
+                filename: None,
+                file_data: None,
+                file_url: None,
+                file_id: Some("file_test_123".into()),
+            },
+        )]),
+        status: None,
+    }));
+
+    assert_eq!(
+        serde_json::to_value(&item).unwrap(),
+        serde_json::json!({
+            "type": "message",
+            "role": "user",
+            "content": [{ "type": "input_file", "file_id": "file_test_123" }]
+        })
+    );
+}
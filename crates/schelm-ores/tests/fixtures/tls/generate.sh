@@ -0,0 +1,37 @@
+#!/bin/sh
+# Regenerates the certificates used by tests/network.rs.
+#
+# The CA signs a server certificate for localhost and a client certificate for
+# mutual TLS. Everything is valid for 100 years.
+set -eu
+cd "$(dirname "$0")"
+
+days=36500
+key() { openssl genpkey -algorithm EC -pkeyopt ec_paramgen_curve:P-256 -out "$1"; }
+
+key ca.key
+openssl req -x509 -new -key ca.key -days "$days" -subj "/CN=schelm-ores test CA" \
+    -addext "basicConstraints=critical,CA:TRUE" \
+    -addext "keyUsage=critical,keyCertSign,cRLSign" \
+    -out ca.pem
+
+issue() {
+    name=$1 cn=$2 ext=$3
+    key "$name.key"
+    openssl req -new -key "$name.key" -subj "/CN=$cn" -out "$name.csr"
+    printf '%s\n' "$ext" > "$name.ext"
+    openssl x509 -req -in "$name.csr" -CA ca.pem -CAkey ca.key -CAcreateserial \
+        -days "$days" -extfile "$name.ext" -out "$name.pem"
+    rm "$name.csr" "$name.ext"
+}
+
+issue server localhost "basicConstraints=CA:FALSE
+keyUsage=critical,digitalSignature
+extendedKeyUsage=serverAuth
+subjectAltName=DNS:localhost,IP:127.0.0.1"
+
+issue client schelm-ores-test-client "basicConstraints=CA:FALSE
+keyUsage=critical,digitalSignature
+extendedKeyUsage=clientAuth"
+
+rm -f ca.key ca.srl
@@ -0,0 +1,318 @@
+pub mod common;
+
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+
+use futures_core::Stream;
+use reqwest::header::HeaderValue;
+use wiremock::matchers::{body_partial_json, header, method, path};
+use wiremock::{Mock, ResponseTemplate};
+
+use schelm_ores::client::{
+    Error, InterceptedRequest, Interceptor, RequestInfo, ResponseMeta, Result,
+};
+use schelm_ores::models::StreamingEvent;
+
+/// Records every hook invocation, prefixed with the interceptor's name.
+#[derive(Clone)]
+struct Recorder {
+    name: &'static str,
+    log: Arc<Mutex<Vec<String>>>,
+}
+
+impl Recorder {
+    fn record(&self, entry: String) {
+        self.log
+            .lock()
+            .unwrap()
+            .push(format!("{}:{entry}", self.name));
+    }
+}
+
+impl Interceptor for Recorder {
+    fn on_request(&self, request: &mut InterceptedRequest) -> Result<()> {
+        self.record(format!("request#{}", request.attempt()));
+        Ok(())
+    }
+
+    fn on_response(&self, _request: &RequestInfo, response: &ResponseMeta) {
+        self.record(format!("response {}", response.status.as_u16()));
+    }
+
+    fn on_error(&self, _request: &RequestInfo, _error: &Error) {
+        self.record("error".to_owned());
+    }
+
+    fn on_stream_event(&self, _request: &RequestInfo, event: &StreamingEvent) {
+        if let StreamingEvent::ResponseOutputTextDelta { delta, .. } = event {
+            self.record(format!("delta {delta}"));
+        }
+    }
+}
+
+/// Injects a header and rewrites the request body.
+struct Rewriter;
+
+impl Interceptor for Rewriter {
+    fn on_request(&self, request: &mut InterceptedRequest) -> Result<()> {
+        request
+            .headers_mut()
+            .insert("x-tenant", HeaderValue::from_static("acme"));
+        if let Some(body) = request.json_mut() {
+            body["metadata"] = serde_json::json!({ "audited": "yes" });
+        }
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn interceptor_mutates_headers_and_body() {
+    let server = common::mock_server().await;
+
+    Mock::given(method("POST"))
+        .and(path("/responses"))
+        .and(header("x-tenant", "acme"))
+        .and(header("authorization", "Bearer test-api-key"))
+        .and(body_partial_json(serde_json::json!({
+            "model": "gpt-test",
+            "metadata": { "audited": "yes" }
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(common::success_response_body()))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::test_client_builder(&server)
+        .interceptor(Rewriter)
+        .build()
+        .unwrap();
+    client
+        .responses()
+        .create_text("gpt-test", "hello")
+        .send()
+        .await
+        .expect("request should succeed");
+}
+
+#[tokio::test]
+async fn hooks_run_in_defined_order() {
+    let server = common::mock_server().await;
+
+    Mock::given(method("POST"))
+        .and(path("/responses"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(common::success_response_body()))
+        .mount(&server)
+        .await;
+
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let client = common::test_client_builder(&server)
+        .interceptor(Recorder {
+            name: "outer",
+            log: log.clone(),
+        })
+        .interceptor(Recorder {
+            name: "inner",
+            log: log.clone(),
+        })
+        .build()
+        .unwrap();
+    client
+        .responses()
+        .create_text("gpt-test", "hello")
+        .send()
+        .await
+        .expect("request should succeed");
+
+    assert_eq!(
+        *log.lock().unwrap(),
+        [
+            "outer:request#1",
+            "inner:request#1",
+            "inner:response 200",
+            "outer:response 200",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn interceptor_can_reroute_requests() {
+    let primary = common::mock_server().await;
+    let canary = common::mock_server().await;
+
+    Mock::given(method("POST"))
+        .and(path("/responses"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(common::success_response_body()))
+        .expect(1)
+        .mount(&canary)
+        .await;
+
+    struct Canary(url::Url);
+
+    impl Interceptor for Canary {
+        fn on_request(&self, request: &mut InterceptedRequest) -> Result<()> {
+            let url = request.url_mut();
+            url.set_port(self.0.port()).unwrap();
+            Ok(())
+        }
+    }
+
+    let canary_url = url::Url::parse(&canary.uri()).unwrap();
+    let client = common::test_client_builder(&primary)
+        .interceptor(Canary(canary_url))
+        .build()
+        .unwrap();
+    client
+        .responses()
+        .create_text("gpt-test", "hello")
+        .send()
+        .await
+        .expect("request should be routed to the canary");
+
+    assert!(primary.received_requests().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn failing_interceptor_aborts_request() {
+    let server = common::mock_server().await;
+
+    struct Reject;
+
+    impl Interceptor for Reject {
+        fn on_request(&self, _request: &mut InterceptedRequest) -> Result<()> {
+            Err(Error::Interceptor("signing key unavailable".into()))
+        }
+    }
+
+    let client = common::test_client_builder(&server)
+        .interceptor(Reject)
+        .build()
+        .unwrap();
+    let err = client
+        .responses()
+        .create_text("gpt-test", "hello")
+        .send()
+        .await
+        .expect_err("request should be rejected");
+
+    assert!(
+        matches!(err, Error::Interceptor(_)),
+        "expected Interceptor error, got: {err:?}"
+    );
+    assert!(server.received_requests().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn interceptor_observes_connection_errors() {
+    // Bind and drop a listener so that nothing accepts on the port.
+    let port = std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+    let base_url = url::Url::parse(&format!("http://127.0.0.1:{port}")).unwrap();
+
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let client = schelm_ores::client::Client::builder("test-api-key", base_url)
+        .interceptor(Recorder {
+            name: "audit",
+            log: log.clone(),
+        })
+        .build()
+        .unwrap();
+    client
+        .responses()
+        .create_text("gpt-test", "hello")
+        .send()
+        .await
+        .expect_err("connection should be refused");
+
+    assert_eq!(*log.lock().unwrap(), ["audit:request#1", "audit:error"]);
+}
+
+#[tokio::test]
+async fn interceptor_sees_idempotency_key_of_failed_requests() {
+    struct KeyRecorder(Arc<Mutex<Vec<Option<String>>>>);
+
+    impl Interceptor for KeyRecorder {
+        fn on_error(&self, request: &RequestInfo, _error: &Error) {
+            self.0.lock().unwrap().push(request.idempotency_key.clone());
+        }
+    }
+
+    let port = std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+    let base_url = url::Url::parse(&format!("http://127.0.0.1:{port}")).unwrap();
+
+    let keys = Arc::new(Mutex::new(Vec::new()));
+    let client = schelm_ores::client::Client::builder("test-api-key", base_url)
+        .interceptor(KeyRecorder(keys.clone()))
+        .build()
+        .unwrap();
+    client
+        .responses()
+        .create_text("gpt-test", "hello")
+        .idempotency_key("order-42")
+        .send()
+        .await
+        .expect_err("connection should be refused");
+
+    assert_eq!(*keys.lock().unwrap(), [Some("order-42".to_owned())]);
+}
+
+#[tokio::test]
+async fn interceptor_observes_stream_events() {
+    let server = common::mock_server().await;
+    let mut body = String::new();
+    for (seq, delta) in ["Hello", " world"].iter().enumerate() {
+        let event = serde_json::json!({
+            "type": "response.output_text.delta",
+            "sequence_number": seq,
+            "item_id": "msg_001",
+            "output_index": 0,
+            "content_index": 0,
+            "delta": delta,
+            "logprobs": []
+        });
+        body.push_str(&format!(
+            "event: response.output_text.delta\ndata: {event}\n\n"
+        ));
+    }
+    body.push_str("data: [DONE]\n\n");
+
+    Mock::given(method("POST"))
+        .and(path("/responses"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(body, "text/event-stream"))
+        .mount(&server)
+        .await;
+
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let client = common::test_client_builder(&server)
+        .interceptor(Recorder {
+            name: "audit",
+            log: log.clone(),
+        })
+        .build()
+        .unwrap();
+    let mut stream = client
+        .responses()
+        .create_text("gpt-test", "hello")
+        .send_stream()
+        .await
+        .expect("send_stream should succeed");
+    while let Some(event) = std::future::poll_fn(|cx| Pin::new(&mut stream).poll_next(cx)).await {
+        event.expect("event should decode");
+    }
+
+    assert_eq!(
+        *log.lock().unwrap(),
+        [
+            "audit:request#1",
+            "audit:response 200",
+            "audit:delta Hello",
+            "audit:delta  world",
+        ]
+    );
+}
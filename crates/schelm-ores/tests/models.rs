@@ -0,0 +1,89 @@
+pub mod common;
+
+use wiremock::matchers::{bearer_token, method, path};
+use wiremock::{Mock, ResponseTemplate};
+
+#[tokio::test]
+async fn list_models_returns_typed_models() {
+    let server = common::mock_server().await;
+
+    Mock::given(method("GET"))
+        .and(path("/models"))
+        .and(bearer_token("test-api-key"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "object": "list",
+            "data": [
+                { "id": "gpt-test", "object": "model", "created": 1700000000, "owned_by": "test" },
+                { "id": "local-model", "object": "model" }
+            ]
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::test_client(&server);
+    let models = client
+        .models()
+        .list()
+        .await
+        .expect("request should succeed");
+
+    assert_eq!(models.data.len(), 2);
+    assert_eq!(models.data[0].id, "gpt-test");
+    assert_eq!(models.data[0].created, Some(1700000000));
+    assert_eq!(models.data[0].owned_by.as_deref(), Some("test"));
+    assert_eq!(models.data[1].id, "local-model");
+    assert_eq!(models.data[1].owned_by, None);
+    assert!(!models.has_more);
+}
+
+#[tokio::test]
+async fn retrieve_model_returns_typed_model() {
+    let server = common::mock_server().await;
+
+    Mock::given(method("GET"))
+        .and(path("/models/gpt-test"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "gpt-test",
+            "object": "model",
+            "created": 1700000000,
+            "owned_by": "test"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::test_client(&server);
+    let model = client
+        .models()
+        .retrieve("gpt-test")
+        .await
+        .expect("request should succeed");
+
+    assert_eq!(model.id, "gpt-test");
+    assert_eq!(model.object, "model");
+}
+
+#[tokio::test]
+async fn retrieve_unknown_model_returns_not_found_error() {
+    let server = common::mock_server().await;
+
+    Mock::given(method("GET"))
+        .and(path("/models/does-not-exist"))
+        .respond_with(ResponseTemplate::new(404))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::test_client(&server);
+    let err = client
+        .models()
+        .retrieve("does-not-exist")
+        .await
+        .expect_err("should return an error for 404");
+
+    assert!(
+        matches!(err, schelm_ores::client::Error::NotFound(_)),
+        "expected NotFound error, got: {err:?}"
+    );
+}
@@ -0,0 +1,265 @@
+pub mod common;
+
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::TcpListener;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, ResponseTemplate};
+
+use schelm_ores::client::{Client, ClientBuilder, Error};
+
+const CA: &[u8] = include_bytes!("fixtures/tls/ca.pem");
+const SERVER_CERT: &[u8] = include_bytes!("fixtures/tls/server.pem");
+const SERVER_KEY: &[u8] = include_bytes!("fixtures/tls/server.key");
+const CLIENT_CERT: &[u8] = include_bytes!("fixtures/tls/client.pem");
+const CLIENT_KEY: &[u8] = include_bytes!("fixtures/tls/client.key");
+
+/// Reads an HTTP/1.1 request and answers it with a successful response.
+///
+/// Returns the request head.
+async fn respond<S: AsyncRead + AsyncWrite + Unpin>(mut stream: S) -> std::io::Result<String> {
+    let mut buf = Vec::new();
+    let head_end = loop {
+        let mut chunk = [0; 4096];
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            return Err(std::io::ErrorKind::UnexpectedEof.into());
+        }
+        buf.extend_from_slice(&chunk[..n]);
+        if let Some(pos) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+            break pos + 4;
+        }
+    };
+    let head = String::from_utf8_lossy(&buf[..head_end]).into_owned();
+    let content_length = head
+        .lines()
+        .find_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            name.eq_ignore_ascii_case("content-length")
+                .then(|| value.trim().parse::<usize>().ok())?
+        })
+        .unwrap_or(0);
+    while buf.len() < head_end + content_length {
+        let mut chunk = [0; 4096];
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            break;
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    }
+
+    let body = common::success_response_body().to_string();
+    let response = format!(
+        "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await?;
+    Ok(head)
+}
+
+/// Starts a TLS server for `localhost`, optionally requiring client certificates.
+async fn tls_server(require_client_cert: bool) -> SocketAddr {
+    let provider = Arc::new(rustls::crypto::aws_lc_rs::default_provider());
+    let certs: Vec<_> = CertificateDer::pem_slice_iter(SERVER_CERT)
+        .collect::<Result<_, _>>()
+        .unwrap();
+    let key = PrivateKeyDer::from_pem_slice(SERVER_KEY).unwrap();
+
+    let builder = rustls::ServerConfig::builder_with_provider(provider.clone())
+        .with_safe_default_protocol_versions()
+        .unwrap();
+    let builder = if require_client_cert {
+        let mut roots = rustls::RootCertStore::empty();
+        roots
+            .add(CertificateDer::from_pem_slice(CA).unwrap())
+            .unwrap();
+        let verifier =
+            rustls::server::WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider)
+                .build()
+                .unwrap();
+        builder.with_client_cert_verifier(verifier)
+    } else {
+        builder.with_no_client_auth()
+    };
+    let config = builder.with_single_cert(certs, key).unwrap();
+    let acceptor = tokio_rustls::TlsAcceptor::from(Arc::new(config));
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        loop {
+            let Ok((stream, _)) = listener.accept().await else {
+                return;
+            };
+            let acceptor = acceptor.clone();
+            tokio::spawn(async move {
+                if let Ok(stream) = acceptor.accept(stream).await {
+                    let _ = respond(stream).await;
+                }
+            });
+        }
+    });
+    addr
+}
+
+/// Starts a plain HTTP proxy that answers every request itself and records
+/// the request heads.
+async fn proxy_server() -> (SocketAddr, Arc<Mutex<Vec<String>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let recorder = seen.clone();
+    tokio::spawn(async move {
+        loop {
+            let Ok((stream, _)) = listener.accept().await else {
+                return;
+            };
+            let recorder = recorder.clone();
+            tokio::spawn(async move {
+                if let Ok(head) = respond(stream).await {
+                    recorder.lock().unwrap().push(head);
+                }
+            });
+        }
+    });
+    (addr, seen)
+}
+
+fn tls_client(addr: SocketAddr) -> ClientBuilder {
+    let base_url = url::Url::parse(&format!("https://localhost:{}/v1", addr.port())).unwrap();
+    Client::builder("test-api-key", base_url)
+}
+
+async fn send(client: &Client) -> schelm_ores::client::Result<()> {
+    client
+        .responses()
+        .create_text("gpt-test", "hello")
+        .send()
+        .await
+        .map(|_| ())
+}
+
+#[tokio::test]
+async fn trusts_private_root_certificates() {
+    let addr = tls_server(false).await;
+
+    let client = tls_client(addr)
+        .add_root_certificate_pem(CA)
+        .system_roots(false)
+        .build()
+        .unwrap();
+    send(&client).await.expect("request should succeed");
+}
+
+#[tokio::test]
+async fn private_root_certificates_extend_system_roots() {
+    let addr = tls_server(false).await;
+
+    let client = tls_client(addr)
+        .add_root_certificate_pem(CA)
+        .build()
+        .unwrap();
+    send(&client).await.expect("request should succeed");
+}
+
+#[tokio::test]
+async fn rejects_untrusted_server_certificates() {
+    let addr = tls_server(false).await;
+
+    let client = tls_client(addr).build().unwrap();
+    let err = send(&client).await.expect_err("the test CA is not trusted");
+    assert!(matches!(err, Error::Reqwest(_)), "got: {err:?}");
+}
+
+#[tokio::test]
+async fn presents_client_identity() {
+    let addr = tls_server(true).await;
+
+    let client = tls_client(addr)
+        .add_root_certificate_pem(CA)
+        .system_roots(false)
+        .identity_pem(CLIENT_CERT, CLIENT_KEY)
+        .build()
+        .unwrap();
+    send(&client).await.expect("request should succeed");
+
+    let client = tls_client(addr)
+        .add_root_certificate_pem(CA)
+        .system_roots(false)
+        .build()
+        .unwrap();
+    let err = send(&client)
+        .await
+        .expect_err("the server requires a client certificate");
+    assert!(matches!(err, Error::Reqwest(_)), "got: {err:?}");
+}
+
+#[tokio::test]
+async fn invalid_certificates_fail_the_build() {
+    let err = tls_client("127.0.0.1:1".parse().unwrap())
+        .identity_pem(CLIENT_CERT, "not a key")
+        .build()
+        .expect_err("identity without a key is invalid");
+    assert!(matches!(err, Error::Reqwest(_)), "got: {err:?}");
+}
+
+#[tokio::test]
+async fn sends_requests_through_proxy() {
+    let (proxy, seen) = proxy_server().await;
+
+    let client = Client::builder("test-api-key", "http://api.invalid/v1".parse().unwrap())
+        .proxy(format!("http://user:secret@{proxy}"))
+        .build()
+        .unwrap();
+    send(&client).await.expect("request should succeed");
+
+    let seen = seen.lock().unwrap();
+    assert_eq!(seen.len(), 1);
+    let head = seen[0].to_ascii_lowercase();
+    assert!(
+        head.starts_with("post http://api.invalid/v1/responses http/1.1"),
+        "{head}"
+    );
+    // base64("user:secret")
+    assert!(
+        head.contains("proxy-authorization: basic dxnlcjpzzwnyzxq="),
+        "{head}"
+    );
+}
+
+#[tokio::test]
+async fn no_proxy_hosts_bypass_the_proxy() {
+    let (proxy, seen) = proxy_server().await;
+    let server = common::mock_server().await;
+
+    Mock::given(method("POST"))
+        .and(path("/responses"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(common::success_response_body()))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::test_client_builder(&server)
+        .proxy(format!("http://{proxy}"))
+        .no_proxy("localhost,127.0.0.1")
+        .build()
+        .unwrap();
+    send(&client).await.expect("request should succeed");
+
+    assert!(seen.lock().unwrap().is_empty());
+}
+
+#[test]
+fn no_proxy_without_proxy_is_rejected() {
+    let base_url = url::Url::parse("http://localhost:8080/v1").unwrap();
+    let err = Client::builder("key", base_url)
+        .no_proxy("localhost")
+        .build()
+        .expect_err("no_proxy needs a proxy");
+    assert!(matches!(err, Error::Config(_)), "got: {err:?}");
+}
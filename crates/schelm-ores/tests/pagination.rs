@@ -0,0 +1,153 @@
+pub mod common;
+
+use std::pin::Pin;
+
+use futures_core::Stream;
+use wiremock::matchers::{method, path, query_param, query_param_is_missing};
+use wiremock::{Mock, ResponseTemplate};
+
+use schelm_ores::client::{Order, PageStream};
+use schelm_ores::models::ItemField;
+
+/// Helper to pull the next item from a `PageStream`.
+async fn next<T>(stream: &mut PageStream<T>) -> Option<schelm_ores::client::Result<T>>
+where
+    T: serde::de::DeserializeOwned + Send + 'static,
+{
+    std::future::poll_fn(|cx| Pin::new(&mut *stream).poll_next(cx)).await
+}
+
+fn input_message(id: &str, text: &str) -> serde_json::Value {
+    serde_json::json!({
+        "type": "message",
+        "id": id,
+        "status": "completed",
+        "role": "user",
+        "content": [{ "type": "input_text", "text": text }]
+    })
+}
+
+fn page(items: Vec<serde_json::Value>, has_more: bool) -> serde_json::Value {
+    let first_id = items.first().map(|i| i["id"].clone());
+    let last_id = items.last().map(|i| i["id"].clone());
+    serde_json::json!({
+        "object": "list",
+        "data": items,
+        "first_id": first_id,
+        "last_id": last_id,
+        "has_more": has_more
+    })
+}
+
+#[tokio::test]
+async fn input_items_returns_single_page() {
+    let server = common::mock_server().await;
+
+    Mock::given(method("GET"))
+        .and(path("/responses/resp_test_123/input_items"))
+        .and(query_param("limit", "2"))
+        .and(query_param("order", "asc"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(page(
+            vec![
+                input_message("msg_1", "Hello"),
+                input_message("msg_2", "Again"),
+            ],
+            true,
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::test_client(&server);
+    let page = client
+        .responses()
+        .input_items("resp_test_123")
+        .limit(2)
+        .order(Order::Asc)
+        .send()
+        .await
+        .expect("request should succeed");
+
+    assert_eq!(page.object, "list");
+    assert_eq!(page.data.len(), 2);
+    assert!(page.has_more);
+    assert_eq!(page.last_id.as_deref(), Some("msg_2"));
+    match &page.data[0] {
+        ItemField::Message(message) => assert_eq!(message.id, "msg_1"),
+        other => panic!("expected Message, got: {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn input_items_stream_fetches_pages_lazily() {
+    let server = common::mock_server().await;
+
+    Mock::given(method("GET"))
+        .and(path("/responses/resp_test_123/input_items"))
+        .and(query_param_is_missing("after"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(page(
+            vec![input_message("msg_1", "one"), input_message("msg_2", "two")],
+            true,
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/responses/resp_test_123/input_items"))
+        .and(query_param("after", "msg_2"))
+        .and(query_param("limit", "2"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(page(vec![input_message("msg_3", "three")], false)),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::test_client(&server);
+    let mut stream = client
+        .responses()
+        .input_items("resp_test_123")
+        .limit(2)
+        .into_stream();
+
+    let mut ids = Vec::new();
+    while let Some(item) = next(&mut stream).await {
+        match item.expect("item should parse") {
+            ItemField::Message(message) => ids.push(message.id),
+            other => panic!("expected Message, got: {other:?}"),
+        }
+    }
+
+    assert_eq!(ids, ["msg_1", "msg_2", "msg_3"]);
+}
+
+#[tokio::test]
+async fn input_items_stream_surfaces_errors() {
+    let server = common::mock_server().await;
+
+    Mock::given(method("GET"))
+        .and(path("/responses/resp_test_123/input_items"))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::test_client(&server);
+    let mut stream = client
+        .responses()
+        .input_items("resp_test_123")
+        .into_stream();
+
+    let err = next(&mut stream)
+        .await
+        .expect("expected an item")
+        .expect_err("expected an error");
+    match err {
+        schelm_ores::client::Error::Api(err) => {
+            assert_eq!(err.status.as_u16(), 500);
+        }
+        other => panic!("expected Api error, got: {other:?}"),
+    }
+    assert!(next(&mut stream).await.is_none(), "expected stream to end");
+}
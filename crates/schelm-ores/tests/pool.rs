@@ -0,0 +1,241 @@
+pub mod common;
+
+use std::sync::Arc;
+
+use wiremock::matchers::{bearer_token, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use schelm_ores::client::{
+    Client, ClientPool, Error, InMemoryTransport, RetryPolicy, Selection, TransportError,
+};
+
+async fn mount_success(server: &MockServer, expected: u64) {
+    Mock::given(method("POST"))
+        .and(path("/v1/responses"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(common::success_response_body()))
+        .expect(expected)
+        .mount(server)
+        .await;
+}
+
+fn endpoint(server: &MockServer, api_key: &str) -> Client {
+    let base_url = url::Url::parse(&format!("{}/v1", server.uri())).unwrap();
+    Client::builder(api_key, base_url).build().unwrap()
+}
+
+async fn send(pool: &ClientPool) -> schelm_ores::client::Result<()> {
+    pool.responses()
+        .create_text("gpt-test", "hello")
+        .send()
+        .await
+        .map(|_| ())
+}
+
+#[tokio::test]
+async fn round_robin_rotates_endpoints_and_keys() {
+    let first = common::mock_server().await;
+    let second = common::mock_server().await;
+
+    for key in ["key-a", "key-b"] {
+        Mock::given(method("POST"))
+            .and(path("/v1/responses"))
+            .and(bearer_token(key))
+            .respond_with(ResponseTemplate::new(200).set_body_json(common::success_response_body()))
+            .expect(1)
+            .mount(&first)
+            .await;
+    }
+    mount_success(&second, 2).await;
+
+    let pool = ClientPool::builder()
+        .endpoint(endpoint(&first, "key-a"))
+        .endpoint(endpoint(&second, "key-c"))
+        .endpoint(endpoint(&first, "key-b"))
+        .endpoint(endpoint(&second, "key-c"))
+        .build()
+        .unwrap();
+    for _ in 0..4 {
+        send(&pool).await.expect("request should succeed");
+    }
+}
+
+#[tokio::test]
+async fn weighted_selection_follows_weights() {
+    let heavy = common::mock_server().await;
+    let light = common::mock_server().await;
+    mount_success(&heavy, 6).await;
+    mount_success(&light, 2).await;
+
+    let pool = ClientPool::builder()
+        .selection(Selection::Weighted)
+        .weighted_endpoint(endpoint(&heavy, "key"), 3)
+        .weighted_endpoint(endpoint(&light, "key"), 1)
+        .build()
+        .unwrap();
+    for _ in 0..8 {
+        send(&pool).await.expect("request should succeed");
+    }
+}
+
+#[tokio::test]
+async fn fails_over_and_ejects_unhealthy_endpoints() {
+    let broken = common::mock_server().await;
+    let healthy = common::mock_server().await;
+
+    Mock::given(method("POST"))
+        .and(path("/v1/responses"))
+        .respond_with(ResponseTemplate::new(503))
+        .expect(1)
+        .mount(&broken)
+        .await;
+    mount_success(&healthy, 3).await;
+
+    let pool = ClientPool::builder()
+        .endpoint(endpoint(&broken, "key"))
+        .endpoint(endpoint(&healthy, "key"))
+        .failure_threshold(1)
+        .build()
+        .unwrap();
+    for _ in 0..3 {
+        send(&pool).await.expect("request should fail over");
+    }
+}
+
+#[tokio::test]
+async fn fails_over_on_connection_errors() {
+    let port = std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+    let unreachable = url::Url::parse(&format!("http://127.0.0.1:{port}/v1")).unwrap();
+    let healthy = common::mock_server().await;
+    mount_success(&healthy, 1).await;
+
+    let pool = ClientPool::builder()
+        .endpoint(Client::builder("key", unreachable).build().unwrap())
+        .endpoint(endpoint(&healthy, "key"))
+        .build()
+        .unwrap();
+    send(&pool).await.expect("request should fail over");
+}
+
+#[tokio::test]
+async fn fails_over_and_ejects_on_transport_errors() {
+    let broken = Arc::new(InMemoryTransport::new(|_| {
+        Err(TransportError::retryable("connection reset").into())
+    }));
+    let healthy = common::mock_server().await;
+    mount_success(&healthy, 3).await;
+
+    let base_url = url::Url::parse("http://broken.invalid/v1").unwrap();
+    let pool = ClientPool::builder()
+        .endpoint(
+            Client::builder("key", base_url)
+                .transport(broken.clone())
+                .build()
+                .unwrap(),
+        )
+        .endpoint(endpoint(&healthy, "key"))
+        .failure_threshold(1)
+        .build()
+        .unwrap();
+    for _ in 0..3 {
+        send(&pool).await.expect("request should fail over");
+    }
+    assert_eq!(broken.take_requests().len(), 1);
+}
+
+#[tokio::test]
+async fn fails_over_on_server_errors_regardless_of_retry_policy() {
+    let broken = common::mock_server().await;
+    let healthy = common::mock_server().await;
+
+    Mock::given(method("POST"))
+        .and(path("/v1/responses"))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(1)
+        .mount(&broken)
+        .await;
+    mount_success(&healthy, 1).await;
+
+    let base_url = url::Url::parse(&format!("{}/v1", broken.uri())).unwrap();
+    let pool = ClientPool::builder()
+        .endpoint(
+            Client::builder("key", base_url)
+                .retry_policy(RetryPolicy::none().retryable_statuses(Vec::new()))
+                .build()
+                .unwrap(),
+        )
+        .endpoint(endpoint(&healthy, "key"))
+        .build()
+        .unwrap();
+    send(&pool).await.expect("request should fail over");
+}
+
+#[tokio::test]
+async fn does_not_fail_over_on_client_errors() {
+    let quota = serde_json::json!({
+        "error": {
+            "type": "insufficient_quota",
+            "code": "insufficient_quota",
+            "message": "You exceeded your current quota."
+        }
+    });
+    let cases = [
+        (400, serde_json::json!({})),
+        (409, serde_json::json!({})),
+        (429, serde_json::json!({})),
+        (429, quota),
+    ];
+
+    for (status, body) in cases {
+        let first = common::mock_server().await;
+        let second = common::mock_server().await;
+
+        Mock::given(method("POST"))
+            .and(path("/v1/responses"))
+            .respond_with(ResponseTemplate::new(status).set_body_json(body))
+            .expect(1)
+            .mount(&first)
+            .await;
+        mount_success(&second, 0).await;
+
+        let pool = ClientPool::builder()
+            .endpoint(endpoint(&first, "key"))
+            .endpoint(endpoint(&second, "key"))
+            .build()
+            .unwrap();
+        let err = send(&pool).await.expect_err("error should be returned");
+        assert_eq!(err.api_error().unwrap().status.as_u16(), status);
+    }
+}
+
+#[tokio::test]
+async fn returns_last_error_when_all_endpoints_fail() {
+    let first = common::mock_server().await;
+    let second = common::mock_server().await;
+
+    for (server, status) in [(&first, 503), (&second, 429)] {
+        Mock::given(method("POST"))
+            .and(path("/v1/responses"))
+            .respond_with(ResponseTemplate::new(status))
+            .expect(1)
+            .mount(server)
+            .await;
+    }
+
+    let pool = ClientPool::builder()
+        .endpoint(endpoint(&first, "key"))
+        .endpoint(endpoint(&second, "key"))
+        .build()
+        .unwrap();
+    let err = send(&pool).await.expect_err("all endpoints fail");
+    assert_eq!(err.api_error().unwrap().status.as_u16(), 429);
+}
+
+#[test]
+fn empty_pool_is_rejected() {
+    let err = ClientPool::builder().build().expect_err("no endpoints");
+    assert!(matches!(err, Error::Config(_)), "got: {err:?}");
+}
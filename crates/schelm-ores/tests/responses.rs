@@ -1,8 +1,11 @@
 pub mod common;
 
-use wiremock::matchers::{bearer_token, body_partial_json, header, method, path};
+use wiremock::matchers::{bearer_token, body_partial_json, header, method, path, query_param};
 use wiremock::{Mock, ResponseTemplate};
 
+use schelm_ores::client::endpoints::responses::ModelFallback;
+use schelm_ores::models::{ReasoningEffortEnum, ReasoningParam};
+
 // ---------------------------------------------------------------------------
 // Happy-path tests
 // ---------------------------------------------------------------------------
@@ -40,6 +43,52 @@ async fn create_response_returns_parsed_resource() {
     assert_eq!(usage.total_tokens, 15);
 }
 
+#[tokio::test]
+async fn send_with_meta_returns_rate_limit_headers() {
+    let server = common::mock_server().await;
+
+    Mock::given(method("POST"))
+        .and(path("/responses"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("x-request-id", "req_meta_1")
+                .insert_header("openai-processing-ms", "1250")
+                .insert_header("x-ratelimit-limit-requests", "500")
+                .insert_header("x-ratelimit-remaining-requests", "499")
+                .insert_header("x-ratelimit-remaining-tokens", "29000")
+                .insert_header("x-ratelimit-reset-tokens", "1m30s")
+                .set_body_json(common::success_response_body()),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::test_client(&server);
+    let resp = client
+        .responses()
+        .create_text("gpt-test", "Say hello")
+        .send_with_meta()
+        .await
+        .expect("request should succeed");
+
+    assert_eq!(resp.data.id, "resp_test_123");
+    assert_eq!(resp.meta.status.as_u16(), 200);
+    assert_eq!(resp.meta.request_id.as_deref(), Some("req_meta_1"));
+    assert_eq!(
+        resp.meta.processing_time,
+        Some(std::time::Duration::from_millis(1250))
+    );
+    let rate_limit = &resp.meta.rate_limit;
+    assert_eq!(rate_limit.limit_requests, Some(500));
+    assert_eq!(rate_limit.remaining_requests, Some(499));
+    assert_eq!(rate_limit.limit_tokens, None);
+    assert_eq!(rate_limit.remaining_tokens, Some(29000));
+    assert_eq!(
+        rate_limit.reset_tokens,
+        Some(std::time::Duration::from_secs(90))
+    );
+}
+
 #[tokio::test]
 async fn create_response_sends_correct_request_body() {
     let server = common::mock_server().await;
@@ -92,10 +141,587 @@ async fn create_response_with_optional_params() {
     assert_eq!(resp.id, "resp_test_123");
 }
 
+#[tokio::test]
+async fn input_tokens_counts_request_without_generating() {
+    let server = common::mock_server().await;
+
+    Mock::given(method("POST"))
+        .and(path("/responses/input_tokens"))
+        .and(body_partial_json(serde_json::json!({
+            "model": "gpt-test",
+            "input": "Say hello",
+            "instructions": "Be concise"
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "object": "response.input_tokens",
+            "input_tokens": 42
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::test_client(&server);
+    let request = client
+        .responses()
+        .create_text("gpt-test", "Say hello")
+        .instructions("Be concise")
+        .temperature(0.2);
+    let count = client
+        .responses()
+        .input_tokens(&request)
+        .await
+        .expect("request should succeed");
+
+    assert_eq!(count.object, "response.input_tokens");
+    assert_eq!(count.input_tokens, 42);
+
+    let received = server.received_requests().await.unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&received[0].body).unwrap();
+    assert!(body.get("temperature").is_none(), "body was: {body}");
+    assert!(body.get("stream").is_none(), "body was: {body}");
+}
+
+#[tokio::test]
+async fn input_tokens_applies_extras_and_validation() {
+    let server = common::mock_server().await;
+
+    Mock::given(method("POST"))
+        .and(path("/responses/input_tokens"))
+        .and(query_param("api-version", "2025-01-01"))
+        .and(header("x-vendor-feature", "enabled"))
+        .and(body_partial_json(serde_json::json!({
+            "model": "gpt-test",
+            "guardrails": { "mode": "strict" }
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "object": "response.input_tokens",
+            "input_tokens": 42
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::test_client(&server);
+    let request = client
+        .responses()
+        .create_text("gpt-test", "hello")
+        .extra_body("guardrails", serde_json::json!({ "mode": "strict" }))
+        .extra_header("x-vendor-feature", "enabled")
+        .extra_query("api-version", "2025-01-01");
+    let count = client
+        .responses()
+        .input_tokens(&request)
+        .await
+        .expect("request should succeed");
+    assert_eq!(count.input_tokens, 42);
+
+    let invalid = client
+        .responses()
+        .create_text("gpt-test", "hello")
+        .extra_body("model", "other-model");
+    let err = client
+        .responses()
+        .input_tokens(&invalid)
+        .await
+        .expect_err("should fail validation");
+    assert!(
+        matches!(err, schelm_ores::client::Error::Validation { .. }),
+        "got: {err:?}"
+    );
+}
+
+#[tokio::test]
+async fn retrieve_response_returns_parsed_resource() {
+    let server = common::mock_server().await;
+
+    Mock::given(method("GET"))
+        .and(path("/responses/resp_test_123"))
+        .and(bearer_token("test-api-key"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(common::success_response_body()))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::test_client(&server);
+    let resp = client
+        .responses()
+        .retrieve("resp_test_123")
+        .send()
+        .await
+        .expect("request should succeed");
+
+    assert_eq!(resp.id, "resp_test_123");
+    assert_eq!(resp.status, "completed");
+}
+
+#[tokio::test]
+async fn retrieve_response_sends_include_query() {
+    let server = common::mock_server().await;
+
+    Mock::given(method("GET"))
+        .and(path("/responses/resp_test_123"))
+        .and(query_param("include[]", "reasoning.encrypted_content"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(common::success_response_body()))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::test_client(&server);
+    client
+        .responses()
+        .retrieve("resp_test_123")
+        .include(vec![
+            schelm_ores::models::IncludeEnum::ReasoningEncryptedContent,
+        ])
+        .send()
+        .await
+        .expect("request should succeed");
+}
+
+#[tokio::test]
+async fn create_background_response_sets_background_and_store() {
+    let server = common::mock_server().await;
+
+    let mut queued = common::success_response_body();
+    queued["status"] = serde_json::json!("queued");
+    queued["background"] = serde_json::json!(true);
+
+    Mock::given(method("POST"))
+        .and(path("/responses"))
+        .and(body_partial_json(serde_json::json!({
+            "background": true,
+            "store": true
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(queued))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::test_client(&server);
+    let resp = client
+        .responses()
+        .create_text("gpt-test", "Think hard")
+        .background(true)
+        .send()
+        .await
+        .expect("request should succeed");
+
+    assert_eq!(resp.status, "queued");
+    assert!(resp.background);
+}
+
+#[tokio::test]
+async fn cancel_response_posts_to_cancel_endpoint() {
+    let server = common::mock_server().await;
+
+    let mut cancelled = common::success_response_body();
+    cancelled["status"] = serde_json::json!("cancelled");
+
+    Mock::given(method("POST"))
+        .and(path("/responses/resp_test_123/cancel"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(cancelled))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::test_client(&server);
+    let resp = client
+        .responses()
+        .cancel("resp_test_123")
+        .await
+        .expect("request should succeed");
+
+    assert_eq!(resp.status, "cancelled");
+}
+
+#[tokio::test]
+async fn delete_response_returns_confirmation() {
+    let server = common::mock_server().await;
+
+    Mock::given(method("DELETE"))
+        .and(path("/responses/resp_test_123"))
+        .and(bearer_token("test-api-key"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "resp_test_123",
+            "object": "response",
+            "deleted": true
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::test_client(&server);
+    let deleted = client
+        .responses()
+        .delete("resp_test_123")
+        .await
+        .expect("request should succeed");
+
+    assert_eq!(deleted.id, "resp_test_123");
+    assert_eq!(deleted.object, "response");
+    assert!(deleted.deleted);
+}
+
+#[tokio::test]
+async fn wait_until_terminal_polls_until_completed() {
+    let server = common::mock_server().await;
+
+    let mut in_progress = common::success_response_body();
+    in_progress["status"] = serde_json::json!("in_progress");
+
+    Mock::given(method("GET"))
+        .and(path("/responses/resp_test_123"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(in_progress))
+        .up_to_n_times(2)
+        .expect(2)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/responses/resp_test_123"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(common::success_response_body()))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::test_client(&server);
+    let resp = client
+        .responses()
+        .wait_until_terminal("resp_test_123")
+        .poll_interval(std::time::Duration::from_millis(1))
+        .send()
+        .await
+        .expect("polling should succeed");
+
+    assert_eq!(resp.status, "completed");
+}
+
+#[tokio::test]
+async fn wait_until_terminal_survives_extreme_backoff() {
+    let server = common::mock_server().await;
+
+    let mut in_progress = common::success_response_body();
+    in_progress["status"] = serde_json::json!("in_progress");
+
+    Mock::given(method("GET"))
+        .and(path("/responses/resp_test_123"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(in_progress))
+        .up_to_n_times(3)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/responses/resp_test_123"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(common::success_response_body()))
+        .mount(&server)
+        .await;
+
+    let client = common::test_client(&server);
+    for multiplier in [f64::INFINITY, f64::NAN, f64::MAX] {
+        client
+            .responses()
+            .wait_until_terminal("resp_test_123")
+            .poll_interval(std::time::Duration::from_millis(1))
+            .max_poll_interval(std::time::Duration::from_millis(2))
+            .backoff_multiplier(multiplier)
+            .send()
+            .await
+            .expect("polling should succeed");
+    }
+}
+
+#[tokio::test]
+async fn wait_until_terminal_times_out() {
+    let server = common::mock_server().await;
+
+    let mut queued = common::success_response_body();
+    queued["status"] = serde_json::json!("queued");
+
+    Mock::given(method("GET"))
+        .and(path("/responses/resp_test_123"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(queued))
+        .mount(&server)
+        .await;
+
+    let client = common::test_client(&server);
+    let err = client
+        .responses()
+        .wait_until_terminal("resp_test_123")
+        .poll_interval(std::time::Duration::from_millis(5))
+        .timeout(std::time::Duration::from_millis(20))
+        .send()
+        .await
+        .expect_err("polling should time out");
+
+    match err {
+        schelm_ores::client::Error::PollTimeout { id, status } => {
+            assert_eq!(id, "resp_test_123");
+            assert_eq!(status, "queued");
+        }
+        other => panic!("expected PollTimeout error, got: {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn create_response_sends_all_optional_params() {
+    let server = common::mock_server().await;
+
+    Mock::given(method("POST"))
+        .and(path("/responses"))
+        .and(body_partial_json(serde_json::json!({
+            "include": ["message.output_text.logprobs"],
+            "metadata": { "team": "search" },
+            "presence_penalty": 0.5,
+            "frequency_penalty": -0.5,
+            "parallel_tool_calls": false,
+            "max_tool_calls": 3,
+            "safety_identifier": "user-hash",
+            "prompt_cache_key": "cache-key",
+            "store": true,
+            "top_logprobs": 5
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(common::success_response_body()))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::test_client(&server);
+    client
+        .responses()
+        .create_text("gpt-test", "Say hello")
+        .include(vec![
+            schelm_ores::models::IncludeEnum::MessageOutputTextLogprobs,
+        ])
+        .metadata([("team".to_owned(), "search".to_owned())].into())
+        .presence_penalty(0.5)
+        .frequency_penalty(-0.5)
+        .parallel_tool_calls(false)
+        .max_tool_calls(3)
+        .safety_identifier("user-hash")
+        .prompt_cache_key("cache-key")
+        .store(true)
+        .top_logprobs(5)
+        .send()
+        .await
+        .expect("request should succeed");
+}
+
 // ---------------------------------------------------------------------------
 // Error-path tests
 // ---------------------------------------------------------------------------
 
+#[tokio::test]
+async fn invalid_params_fail_validation_before_sending() {
+    let server = common::mock_server().await;
+
+    Mock::given(method("POST"))
+        .and(path("/responses"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(common::success_response_body()))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let client = common::test_client(&server);
+    let err = client
+        .responses()
+        .create_text("gpt-test", "hello")
+        .temperature(3.0)
+        .top_p(1.5)
+        .max_output_tokens(0)
+        .send()
+        .await
+        .expect_err("should fail validation");
+
+    match err {
+        schelm_ores::client::Error::Validation { errors } => {
+            let fields: Vec<_> = errors.iter().map(|e| e.field.as_str()).collect();
+            assert_eq!(fields, ["temperature", "top_p", "max_output_tokens"]);
+        }
+        other => panic!("expected Validation error, got: {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn create_response_sends_extra_body_headers_and_query() {
+    let server = common::mock_server().await;
+
+    Mock::given(method("POST"))
+        .and(path("/responses"))
+        .and(query_param("api-version", "2025-01-01"))
+        .and(header("x-vendor-feature", "enabled"))
+        .and(body_partial_json(serde_json::json!({
+            "model": "gpt-test",
+            "top_k": 40,
+            "guardrails": { "mode": "strict" }
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(common::success_response_body()))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::test_client(&server);
+    client
+        .responses()
+        .create_text("gpt-test", "hello")
+        .extra_body("top_k", 40)
+        .extra_body("guardrails", serde_json::json!({ "mode": "strict" }))
+        .extra_header("x-vendor-feature", "enabled")
+        .extra_query("api-version", "2025-01-01")
+        .send()
+        .await
+        .expect("request should succeed");
+}
+
+#[tokio::test]
+async fn extras_conflicting_with_typed_fields_fail_validation() {
+    let server = common::mock_server().await;
+
+    Mock::given(method("POST"))
+        .and(path("/responses"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(common::success_response_body()))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let client = common::test_client(&server);
+    let err = client
+        .responses()
+        .create_text("gpt-test", "hello")
+        .temperature(0.5)
+        .extra_body("temperature", 0.7)
+        .extra_body("model", "other-model")
+        .extra_header("content-type", "text/plain")
+        .send()
+        .await
+        .expect_err("should fail validation");
+
+    match err {
+        schelm_ores::client::Error::Validation { errors } => {
+            let fields: Vec<_> = errors.iter().map(|e| e.field.as_str()).collect();
+            assert_eq!(
+                fields,
+                [
+                    "extra_body.model",
+                    "extra_body.temperature",
+                    "extra_headers.content-type"
+                ]
+            );
+        }
+        other => panic!("expected Validation error, got: {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn falls_back_to_next_model_on_capacity_errors() {
+    let server = common::mock_server().await;
+
+    Mock::given(method("POST"))
+        .and(path("/responses"))
+        .and(body_partial_json(
+            serde_json::json!({ "model": "gpt-large" }),
+        ))
+        .respond_with(ResponseTemplate::new(429).set_body_json(serde_json::json!({
+            "error": { "type": "rate_limit_error", "message": "Rate limit exceeded" }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/responses"))
+        .and(body_partial_json(
+            serde_json::json!({ "model": "gpt-medium" }),
+        ))
+        .respond_with(ResponseTemplate::new(503))
+        .expect(1)
+        .mount(&server)
+        .await;
+    let mut body = common::success_response_body();
+    body["model"] = "gpt-small".into();
+    Mock::given(method("POST"))
+        .and(path("/responses"))
+        .and(body_partial_json(serde_json::json!({
+            "model": "gpt-small",
+            "reasoning": { "effort": "low" },
+            "instructions": "be brief"
+        })))
+        .and(header("idempotency-key", "order-42-fallback-2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(body))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let resp = common::test_client(&server)
+        .responses()
+        .create_text("gpt-large", "hello")
+        .instructions("be brief")
+        .idempotency_key("order-42")
+        .fallback("gpt-medium")
+        .fallback(ModelFallback::new("gpt-small").reasoning(ReasoningParam {
+            effort: Some(ReasoningEffortEnum::Low),
+            summary: None,
+        }))
+        .send()
+        .await
+        .expect("fallback model should answer");
+
+    assert_eq!(resp.model, "gpt-small");
+}
+
+#[tokio::test]
+async fn falls_back_on_context_length_errors_only_while_models_remain() {
+    let server = common::mock_server().await;
+
+    Mock::given(method("POST"))
+        .and(path("/responses"))
+        .respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({
+            "error": {
+                "type": "invalid_request_error",
+                "code": "context_length_exceeded",
+                "message": "Input is too long"
+            }
+        })))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let err = common::test_client(&server)
+        .responses()
+        .create_text("gpt-small", "hello")
+        .fallback("gpt-large-context")
+        .send()
+        .await
+        .expect_err("every model rejects the input");
+
+    assert!(err.api_error().unwrap().is_context_length_exceeded());
+    let models: Vec<_> = server
+        .received_requests()
+        .await
+        .unwrap()
+        .iter()
+        .map(|r| r.body_json::<serde_json::Value>().unwrap()["model"].clone())
+        .collect();
+    assert_eq!(models, ["gpt-small", "gpt-large-context"]);
+}
+
+#[tokio::test]
+async fn other_errors_do_not_fall_back() {
+    let server = common::mock_server().await;
+
+    Mock::given(method("POST"))
+        .and(path("/responses"))
+        .respond_with(ResponseTemplate::new(401))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let err = common::test_client(&server)
+        .responses()
+        .create_text("gpt-large", "hello")
+        .fallback("gpt-small")
+        .send()
+        .await
+        .expect_err("401 should be returned");
+    assert!(err.api_error().unwrap().is_auth());
+}
+
 #[tokio::test]
 async fn returns_http_status_error_on_401() {
     let server = common::mock_server().await;
@@ -123,11 +749,15 @@ async fn returns_http_status_error_on_401() {
         .expect_err("should return an error for 401");
 
     match err {
-        schelm_ores::client::Error::HttpStatus { status, body } => {
-            assert_eq!(status.as_u16(), 401);
-            assert!(body.contains("invalid_api_key"), "body was: {body}");
+        schelm_ores::client::Error::Api(err) => {
+            assert_eq!(err.status.as_u16(), 401);
+            assert!(
+                err.body.contains("invalid_api_key"),
+                "body was: {}",
+                err.body
+            );
         }
-        other => panic!("expected HttpStatus error, got: {other:?}"),
+        other => panic!("expected Api error, got: {other:?}"),
     }
 }
 
@@ -158,11 +788,15 @@ async fn returns_http_status_error_on_400() {
         .expect_err("should return an error for 400");
 
     match err {
-        schelm_ores::client::Error::HttpStatus { status, body } => {
-            assert_eq!(status.as_u16(), 400);
-            assert!(body.contains("invalid_request"), "body was: {body}");
+        schelm_ores::client::Error::Api(err) => {
+            assert_eq!(err.status.as_u16(), 400);
+            assert!(
+                err.body.contains("invalid_request"),
+                "body was: {}",
+                err.body
+            );
         }
-        other => panic!("expected HttpStatus error, got: {other:?}"),
+        other => panic!("expected Api error, got: {other:?}"),
     }
 }
 
@@ -193,11 +827,11 @@ async fn returns_http_status_error_on_500() {
         .expect_err("should return an error for 500");
 
     match err {
-        schelm_ores::client::Error::HttpStatus { status, body } => {
-            assert_eq!(status.as_u16(), 500);
-            assert!(body.contains("server_error"), "body was: {body}");
+        schelm_ores::client::Error::Api(err) => {
+            assert_eq!(err.status.as_u16(), 500);
+            assert!(err.body.contains("server_error"), "body was: {}", err.body);
         }
-        other => panic!("expected HttpStatus error, got: {other:?}"),
+        other => panic!("expected Api error, got: {other:?}"),
     }
 }
 
@@ -228,11 +862,20 @@ async fn returns_http_status_error_on_429_rate_limit() {
         .expect_err("should return an error for 429");
 
     match err {
-        schelm_ores::client::Error::HttpStatus { status, body } => {
-            assert_eq!(status.as_u16(), 429);
-            assert!(body.contains("rate_limit_exceeded"), "body was: {body}");
+        schelm_ores::client::Error::Api(err) => {
+            assert_eq!(err.status.as_u16(), 429);
+            assert!(
+                err.body.contains("rate_limit_exceeded"),
+                "body was: {}",
+                err.body
+            );
+            assert_eq!(err.error_type.as_deref(), Some("rate_limit_error"));
+            assert_eq!(err.code.as_deref(), Some("rate_limit_exceeded"));
+            assert_eq!(err.message.as_deref(), Some("Rate limit exceeded"));
+            assert!(err.is_rate_limited());
+            assert!(err.is_retryable());
         }
-        other => panic!("expected HttpStatus error, got: {other:?}"),
+        other => panic!("expected Api error, got: {other:?}"),
     }
 }
 
@@ -255,10 +898,9 @@ async fn returns_error_on_invalid_json_response() {
         .await
         .expect_err("should return an error for invalid JSON");
 
-    // reqwest surfaces deserialization failures as its own error type
     assert!(
-        matches!(err, schelm_ores::client::Error::Reqwest(_)),
-        "expected Reqwest error, got: {err:?}"
+        matches!(err, schelm_ores::client::Error::Json(_)),
+        "expected Json error, got: {err:?}"
     );
 }
 
@@ -282,18 +924,85 @@ async fn returns_http_status_error_with_empty_body() {
         .expect_err("should return an error for 502");
 
     match err {
-        schelm_ores::client::Error::HttpStatus { status, body } => {
-            assert_eq!(status.as_u16(), 502);
-            assert!(body.is_empty(), "expected empty body, got: {body}");
+        schelm_ores::client::Error::Api(err) => {
+            assert_eq!(err.status.as_u16(), 502);
+            assert!(
+                err.body.is_empty(),
+                "expected empty body, got: {}",
+                err.body
+            );
         }
-        other => panic!("expected HttpStatus error, got: {other:?}"),
+        other => panic!("expected Api error, got: {other:?}"),
     }
 }
 
+#[tokio::test]
+async fn api_error_carries_request_id_and_param() {
+    let server = common::mock_server().await;
+    let error_body = serde_json::json!({
+        "error": {
+            "message": "Unsupported parameter: 'temperature'.",
+            "type": "invalid_request_error",
+            "param": "temperature",
+            "code": "unsupported_parameter"
+        }
+    });
+
+    Mock::given(method("POST"))
+        .and(path("/responses"))
+        .respond_with(
+            ResponseTemplate::new(400)
+                .insert_header("x-request-id", "req_abc123")
+                .set_body_json(error_body),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::test_client(&server);
+    let err = client
+        .responses()
+        .create_text("gpt-test", "hello")
+        .send()
+        .await
+        .expect_err("should return an error for 400");
+
+    let api = err.api_error().expect("expected an API error");
+    assert_eq!(api.request_id.as_deref(), Some("req_abc123"));
+    assert_eq!(api.param.as_deref(), Some("temperature"));
+    assert!(!api.is_auth());
+    assert!(!api.is_retryable());
+    assert!(err.to_string().contains("req_abc123"), "display was: {err}");
+}
+
+#[tokio::test]
+async fn api_error_body_is_truncated() {
+    let server = common::mock_server().await;
+
+    Mock::given(method("POST"))
+        .and(path("/responses"))
+        .respond_with(ResponseTemplate::new(500).set_body_string("x".repeat(1024 * 1024)))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::test_client(&server);
+    let err = client
+        .responses()
+        .create_text("gpt-test", "hello")
+        .send()
+        .await
+        .expect_err("should return an error for 500");
+
+    let api = err.api_error().expect("expected an API error");
+    assert!(api.body_truncated);
+    assert_eq!(api.body.len(), 64 * 1024);
+}
+
 #[tokio::test]
 async fn unmatched_request_returns_error() {
     // When wiremock has no matching mock, it responds with 404.
-    // The client should surface that as an HttpStatus error.
+    // The client should surface that as an Api error.
     let server = common::mock_server().await;
     // Intentionally mount no mocks.
 
@@ -306,9 +1015,73 @@ async fn unmatched_request_returns_error() {
         .expect_err("should return an error when no mock matches");
 
     match err {
-        schelm_ores::client::Error::HttpStatus { status, .. } => {
-            assert_eq!(status.as_u16(), 404);
+        schelm_ores::client::Error::Api(err) => {
+            assert_eq!(err.status.as_u16(), 404);
+        }
+        other => panic!("expected Api error, got: {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn delete_missing_response_returns_not_found_error() {
+    let server = common::mock_server().await;
+    let error_body = serde_json::json!({
+        "error": {
+            "message": "No response found with id 'resp_missing'.",
+            "type": "invalid_request_error",
+            "code": null
+        }
+    });
+
+    Mock::given(method("DELETE"))
+        .and(path("/responses/resp_missing"))
+        .respond_with(ResponseTemplate::new(404).set_body_json(error_body))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::test_client(&server);
+    let err = client
+        .responses()
+        .delete("resp_missing")
+        .await
+        .expect_err("should return an error for 404");
+
+    match err {
+        schelm_ores::client::Error::NotFound(err) => {
+            assert!(err.body.contains("resp_missing"), "body was: {}", err.body);
         }
-        other => panic!("expected HttpStatus error, got: {other:?}"),
+        other => panic!("expected NotFound error, got: {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn missing_response_lookups_return_not_found_error() {
+    let server = common::mock_server().await;
+
+    Mock::given(wiremock::matchers::path_regex("^/responses/resp_missing"))
+        .respond_with(ResponseTemplate::new(404))
+        .expect(3)
+        .mount(&server)
+        .await;
+
+    let client = common::test_client(&server);
+    let responses = client.responses();
+    let errors = [
+        responses.retrieve("resp_missing").send().await.map(|_| ()),
+        responses.cancel("resp_missing").await.map(|_| ()),
+        responses
+            .input_items("resp_missing")
+            .send()
+            .await
+            .map(|_| ()),
+    ];
+
+    for result in errors {
+        let err = result.expect_err("should return an error for 404");
+        assert!(
+            matches!(err, schelm_ores::client::Error::NotFound(_)),
+            "expected NotFound error, got: {err:?}"
+        );
     }
 }
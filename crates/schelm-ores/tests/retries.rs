@@ -0,0 +1,310 @@
+pub mod common;
+
+use std::time::Duration;
+
+use wiremock::matchers::{header, method, path};
+use wiremock::{Mock, ResponseTemplate};
+
+use schelm_ores::client::{Client, Error, RetryPolicy};
+
+/// A retry policy with negligible delays so tests run quickly.
+fn fast_policy() -> RetryPolicy {
+    RetryPolicy::default()
+        .max_attempts(3)
+        .base_delay(Duration::from_millis(1))
+        .max_delay(Duration::from_millis(5))
+}
+
+fn retrying_client(server: &wiremock::MockServer) -> Client {
+    common::test_client_builder(server)
+        .retry_policy(fast_policy())
+        .build()
+        .expect("client builder should not fail with valid inputs")
+}
+
+#[tokio::test]
+async fn retries_retryable_status_until_success() {
+    let server = common::mock_server().await;
+
+    Mock::given(method("POST"))
+        .and(path("/responses"))
+        .respond_with(ResponseTemplate::new(503))
+        .up_to_n_times(2)
+        .expect(2)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/responses"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(common::success_response_body()))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let resp = retrying_client(&server)
+        .responses()
+        .create_text("gpt-test", "hello")
+        .send()
+        .await
+        .expect("request should succeed after retries");
+
+    assert_eq!(resp.id, "resp_test_123");
+}
+
+#[tokio::test]
+async fn returns_last_error_when_attempts_are_exhausted() {
+    let server = common::mock_server().await;
+
+    Mock::given(method("POST"))
+        .and(path("/responses"))
+        .respond_with(ResponseTemplate::new(429).insert_header("retry-after-ms", "1"))
+        .expect(3)
+        .mount(&server)
+        .await;
+
+    let err = retrying_client(&server)
+        .responses()
+        .create_text("gpt-test", "hello")
+        .send()
+        .await
+        .expect_err("should fail after exhausting attempts");
+
+    match err {
+        Error::Api(err) => assert_eq!(err.status.as_u16(), 429),
+        other => panic!("expected Api error, got: {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn honors_retry_after_header() {
+    let server = common::mock_server().await;
+
+    Mock::given(method("POST"))
+        .and(path("/responses"))
+        .respond_with(ResponseTemplate::new(429).insert_header("retry-after", "1"))
+        .up_to_n_times(1)
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/responses"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(common::success_response_body()))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let started = std::time::Instant::now();
+    retrying_client(&server)
+        .responses()
+        .create_text("gpt-test", "hello")
+        .send()
+        .await
+        .expect("request should succeed after retry");
+
+    assert!(
+        started.elapsed() >= Duration::from_secs(1),
+        "retried after {:?}",
+        started.elapsed()
+    );
+}
+
+#[tokio::test]
+async fn does_not_retry_non_retryable_status() {
+    let server = common::mock_server().await;
+
+    Mock::given(method("POST"))
+        .and(path("/responses"))
+        .respond_with(ResponseTemplate::new(400))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let err = retrying_client(&server)
+        .responses()
+        .create_text("gpt-test", "hello")
+        .send()
+        .await
+        .expect_err("should fail without retrying");
+
+    assert!(
+        matches!(err, Error::Api(_)),
+        "expected Api error, got: {err:?}"
+    );
+}
+
+#[tokio::test]
+async fn does_not_retry_quota_errors() {
+    let server = common::mock_server().await;
+
+    Mock::given(method("POST"))
+        .and(path("/responses"))
+        .respond_with(ResponseTemplate::new(429).set_body_json(serde_json::json!({
+            "error": {
+                "type": "insufficient_quota",
+                "code": "insufficient_quota",
+                "message": "You exceeded your current quota."
+            }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let err = retrying_client(&server)
+        .responses()
+        .create_text("gpt-test", "hello")
+        .send()
+        .await
+        .expect_err("should fail without retrying");
+
+    let api_error = err.api_error().expect("expected Api error");
+    assert!(api_error.is_quota_exceeded(), "got: {api_error:?}");
+    assert_eq!(
+        api_error.message.as_deref(),
+        Some("You exceeded your current quota.")
+    );
+}
+
+#[tokio::test]
+async fn custom_retryable_statuses_are_respected() {
+    let server = common::mock_server().await;
+
+    Mock::given(method("POST"))
+        .and(path("/responses"))
+        .respond_with(ResponseTemplate::new(503))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::test_client_builder(&server)
+        .retry_policy(
+            fast_policy().retryable_statuses(vec![reqwest::StatusCode::TOO_MANY_REQUESTS]),
+        )
+        .build()
+        .unwrap();
+    client
+        .responses()
+        .create_text("gpt-test", "hello")
+        .send()
+        .await
+        .expect_err("503 should not be retried");
+}
+
+#[tokio::test]
+async fn send_stream_retries_before_the_stream_starts() {
+    let server = common::mock_server().await;
+
+    Mock::given(method("POST"))
+        .and(path("/responses"))
+        .respond_with(ResponseTemplate::new(502))
+        .up_to_n_times(1)
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/responses"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_raw("data: [DONE]\n\n", "text/event-stream"),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    retrying_client(&server)
+        .responses()
+        .create_text("gpt-test", "hello")
+        .send_stream()
+        .await
+        .expect("stream should open after retry");
+}
+
+#[tokio::test]
+async fn retries_connection_errors() {
+    // Bind and immediately drop a listener to get a port nobody listens on.
+    let addr = std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap();
+    let base_url = url::Url::parse(&format!("http://{addr}")).unwrap();
+    let client = Client::builder("test-api-key", base_url)
+        .retry_policy(
+            fast_policy()
+                .base_delay(Duration::from_millis(50))
+                .max_delay(Duration::from_millis(100))
+                .jitter(0.0),
+        )
+        .build()
+        .unwrap();
+
+    let started = std::time::Instant::now();
+    let err = client
+        .responses()
+        .create_text("gpt-test", "hello")
+        .send()
+        .await
+        .expect_err("connection should be refused");
+
+    assert!(matches!(err, Error::Reqwest(_)), "got: {err:?}");
+    // Three attempts, separated by backoff delays of 50ms and 100ms.
+    assert!(
+        started.elapsed() >= Duration::from_millis(150),
+        "gave up after {:?}",
+        started.elapsed()
+    );
+}
+
+#[tokio::test]
+async fn retries_reuse_the_idempotency_key() {
+    let server = common::mock_server().await;
+
+    Mock::given(method("POST"))
+        .and(path("/responses"))
+        .respond_with(ResponseTemplate::new(503))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/responses"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(common::success_response_body()))
+        .mount(&server)
+        .await;
+
+    retrying_client(&server)
+        .responses()
+        .create_text("gpt-test", "hello")
+        .send()
+        .await
+        .expect("request should succeed after a retry");
+
+    let requests = server.received_requests().await.unwrap();
+    let keys: Vec<_> = requests
+        .iter()
+        .map(|r| r.headers.get("idempotency-key").cloned())
+        .collect();
+    assert_eq!(keys.len(), 2);
+    assert!(keys[0].is_some());
+    assert_eq!(keys[0], keys[1]);
+}
+
+#[tokio::test]
+async fn idempotency_key_is_reported_in_errors() {
+    let server = common::mock_server().await;
+
+    Mock::given(method("POST"))
+        .and(path("/responses"))
+        .and(header("idempotency-key", "order-42"))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(3)
+        .mount(&server)
+        .await;
+
+    let err = retrying_client(&server)
+        .responses()
+        .create_text("gpt-test", "hello")
+        .idempotency_key("order-42")
+        .send()
+        .await
+        .expect_err("should fail after exhausting attempts");
+
+    let api_error = err.api_error().expect("should be an API error");
+    assert_eq!(api_error.idempotency_key.as_deref(), Some("order-42"));
+    assert!(err.to_string().contains("[idempotency key: order-42]"));
+}
@@ -3,7 +3,7 @@ pub mod common;
 use std::pin::Pin;
 
 use futures_core::Stream;
-use wiremock::matchers::{body_partial_json, header, method, path};
+use wiremock::matchers::{body_partial_json, header, method, path, query_param};
 use wiremock::{Mock, ResponseTemplate};
 
 use schelm_ores::client::{Error, ResponseEventStream, StreamingError};
@@ -124,7 +124,7 @@ async fn send_stream_rejects_wrong_content_type() {
 }
 
 // ---------------------------------------------------------------------------
-// 3. Non-2xx error — send_stream returns HttpStatus error
+// 3. Non-2xx error — send_stream returns Api error
 // ---------------------------------------------------------------------------
 
 #[tokio::test]
@@ -153,11 +153,101 @@ async fn send_stream_returns_http_error_on_500() {
         .await;
 
     match result {
-        Err(Error::HttpStatus { status, body }) => {
-            assert_eq!(status.as_u16(), 500);
-            assert!(body.contains("server_error"), "body was: {body}");
+        Err(Error::Api(err)) => {
+            assert_eq!(err.status.as_u16(), 500);
+            assert!(err.body.contains("server_error"), "body was: {}", err.body);
         }
-        Err(other) => panic!("expected HttpStatus error, got: {other:?}"),
+        Err(other) => panic!("expected Api error, got: {other:?}"),
         Ok(_) => panic!("expected error, got Ok"),
     }
 }
+
+// ---------------------------------------------------------------------------
+// 4. Retrieve with stream=true replays stored events
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn retrieve_stream_replays_events() {
+    let server = common::mock_server().await;
+    let body = sse_body(&[text_delta_event(3, "replayed")]);
+
+    Mock::given(method("GET"))
+        .and(path("/responses/resp_test_123"))
+        .and(header("accept", "text/event-stream"))
+        .and(query_param("stream", "true"))
+        .and(query_param("starting_after", "2"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(body, "text/event-stream"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::test_client(&server);
+    let mut stream = client
+        .responses()
+        .retrieve("resp_test_123")
+        .starting_after(2)
+        .send_stream()
+        .await
+        .expect("send_stream should succeed");
+
+    let event = next(&mut stream)
+        .await
+        .expect("expected an event")
+        .expect("event should parse");
+    match &event {
+        StreamingEvent::ResponseOutputTextDelta {
+            delta,
+            sequence_number,
+            ..
+        } => {
+            assert_eq!(delta, "replayed");
+            assert_eq!(*sequence_number, 3);
+        }
+        other => panic!("expected ResponseOutputTextDelta, got: {other:?}"),
+    }
+
+    assert!(next(&mut stream).await.is_none(), "expected stream to end");
+}
+
+// ---------------------------------------------------------------------------
+// Response metadata
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn send_stream_with_meta_returns_headers() {
+    let server = common::mock_server().await;
+    let body = sse_body(&[text_delta_event(0, "Hi")]);
+
+    Mock::given(method("POST"))
+        .and(path("/responses"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("x-request-id", "req_stream_1")
+                .insert_header("x-ratelimit-remaining-requests", "42")
+                .set_body_raw(body, "text/event-stream"),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::test_client(&server);
+    let mut resp = client
+        .responses()
+        .create_text("gpt-test", "hello")
+        .send_stream_with_meta()
+        .await
+        .expect("send_stream_with_meta should succeed");
+
+    assert_eq!(resp.meta.request_id.as_deref(), Some("req_stream_1"));
+    assert_eq!(resp.meta.rate_limit.remaining_requests, Some(42));
+
+    let event = next(&mut resp.data)
+        .await
+        .expect("expected an event")
+        .expect("event should decode");
+    assert!(matches!(
+        event,
+        StreamingEvent::ResponseOutputTextDelta { .. }
+    ));
+    assert!(next(&mut resp.data).await.is_none());
+}
@@ -0,0 +1,110 @@
+pub mod common;
+
+use std::time::Duration;
+
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use schelm_ores::client::{CancellationToken, Error};
+
+async fn mount_delayed(server: &MockServer, delay: Duration) {
+    Mock::given(method("POST"))
+        .and(path("/responses"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(common::success_response_body())
+                .set_delay(delay),
+        )
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn per_request_timeout_aborts_slow_requests() {
+    let server = common::mock_server().await;
+    mount_delayed(&server, Duration::from_secs(5)).await;
+
+    let client = common::test_client(&server);
+    let err = client
+        .responses()
+        .create_text("gpt-test", "hello")
+        .timeout(Duration::from_millis(50))
+        .send()
+        .await
+        .expect_err("request should time out");
+
+    assert!(
+        matches!(&err, Error::Reqwest(e) if e.is_timeout()),
+        "expected timeout, got: {err:?}"
+    );
+}
+
+#[tokio::test]
+async fn per_request_timeout_overrides_client_timeout() {
+    let server = common::mock_server().await;
+    mount_delayed(&server, Duration::from_millis(200)).await;
+
+    let client = common::test_client_builder(&server)
+        .timeout(Duration::from_millis(50))
+        .connect_timeout(Duration::from_secs(1))
+        .build()
+        .unwrap();
+    let resp = client
+        .responses()
+        .create_text("gpt-test", "hello")
+        .timeout(Duration::from_secs(5))
+        .send()
+        .await
+        .expect("longer per-request timeout should apply");
+    assert_eq!(resp.id, "resp_test_123");
+}
+
+#[tokio::test]
+async fn cancellation_aborts_in_flight_requests() {
+    let server = common::mock_server().await;
+    mount_delayed(&server, Duration::from_secs(5)).await;
+
+    let token = CancellationToken::new();
+    let canceller = token.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        canceller.cancel();
+    });
+
+    let client = common::test_client(&server);
+    let started = std::time::Instant::now();
+    let err = client
+        .responses()
+        .create_text("gpt-test", "hello")
+        .cancellation_token(token)
+        .send()
+        .await
+        .expect_err("request should be cancelled");
+
+    assert!(matches!(err, Error::Cancelled), "got: {err:?}");
+    assert!(started.elapsed() < Duration::from_secs(5));
+}
+
+#[tokio::test]
+async fn cancelled_token_sends_nothing() {
+    let server = common::mock_server().await;
+
+    Mock::given(method("POST"))
+        .and(path("/responses"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let token = CancellationToken::new();
+    token.cancel();
+
+    let client = common::test_client(&server);
+    let result = client
+        .responses()
+        .create_text("gpt-test", "hello")
+        .cancellation_token(token)
+        .send_stream()
+        .await;
+    assert!(matches!(result, Err(Error::Cancelled)));
+}
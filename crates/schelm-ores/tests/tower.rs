@@ -0,0 +1,240 @@
+#![cfg(feature = "tower")]
+
+pub mod common;
+
+use std::convert::Infallible;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+
+use bytes::Bytes;
+use futures_core::Stream;
+use tower::{ServiceBuilder, ServiceExt, service_fn};
+use wiremock::matchers::{body_partial_json, header, method, path};
+use wiremock::{Mock, ResponseTemplate};
+
+use schelm_ores::client::{Client, Error, RetryPolicy};
+use schelm_ores::models::{self, StreamingEvent};
+
+fn base_url() -> url::Url {
+    url::Url::parse("http://tower.invalid/v1").unwrap()
+}
+
+fn json_response(body: serde_json::Value) -> http::Response<String> {
+    http::Response::builder()
+        .status(200)
+        .header("content-type", "application/json")
+        .body(body.to_string())
+        .unwrap()
+}
+
+/// Forwards requests to a real server via reqwest.
+async fn forward(
+    request: http::Request<Bytes>,
+) -> Result<http::Response<reqwest::Body>, reqwest::Error> {
+    let request = reqwest::Request::try_from(request)?;
+    let resp = reqwest::Client::new().execute(request).await?;
+    Ok(resp.into())
+}
+
+#[tokio::test]
+async fn client_sends_requests_through_service() {
+    let seen = Arc::new(Mutex::new(None));
+    let recorder = seen.clone();
+    let service = service_fn(move |request: http::Request<Bytes>| {
+        let recorder = recorder.clone();
+        async move {
+            *recorder.lock().unwrap() = Some(request);
+            Ok::<_, Infallible>(json_response(common::success_response_body()))
+        }
+    });
+
+    let client = Client::builder("test-api-key", base_url())
+        .http_service(service)
+        .build()
+        .unwrap();
+    let resp = client
+        .responses()
+        .create_text("gpt-test", "hello")
+        .send()
+        .await
+        .expect("request should succeed");
+    assert_eq!(resp.id, "resp_test_123");
+
+    let request = seen.lock().unwrap().take().expect("service was not called");
+    assert_eq!(request.method(), http::Method::POST);
+    assert_eq!(request.uri(), "http://tower.invalid/v1/responses");
+    assert_eq!(request.headers()["authorization"], "Bearer test-api-key");
+    assert_eq!(request.headers()["content-type"], "application/json");
+    let body: serde_json::Value = serde_json::from_slice(request.body()).unwrap();
+    assert_eq!(body["model"], "gpt-test");
+}
+
+#[tokio::test]
+async fn client_runs_through_tower_layers() {
+    let server = common::mock_server().await;
+
+    Mock::given(method("POST"))
+        .and(path("/responses"))
+        .and(header("x-layer", "applied"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(common::success_response_body()))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let stack = ServiceBuilder::new()
+        .concurrency_limit(4)
+        .map_request(|mut request: http::Request<Bytes>| {
+            request
+                .headers_mut()
+                .insert("x-layer", http::HeaderValue::from_static("applied"));
+            request
+        })
+        .service_fn(forward);
+
+    let client = common::test_client_builder(&server)
+        .http_service(stack)
+        .build()
+        .unwrap();
+    client
+        .responses()
+        .create_text("gpt-test", "hello")
+        .send()
+        .await
+        .expect("request should succeed");
+}
+
+#[tokio::test]
+async fn service_errors_are_transport_errors() {
+    let service = service_fn(|_request: http::Request<Bytes>| async {
+        Err::<http::Response<String>, _>(std::io::Error::other("overloaded"))
+    });
+
+    let client = Client::builder("test-api-key", base_url())
+        .http_service(service)
+        .build()
+        .unwrap();
+    let err = client
+        .responses()
+        .create_text("gpt-test", "hello")
+        .send()
+        .await
+        .expect_err("service error should be returned");
+
+    assert!(
+        matches!(&err, Error::Transport(e) if e.to_string() == "overloaded" && !e.is_retryable()),
+        "expected Transport error, got: {err:?}"
+    );
+}
+
+#[tokio::test]
+async fn connection_errors_from_services_are_retried() {
+    let calls = Arc::new(Mutex::new(0));
+    let counter = calls.clone();
+    let service = service_fn(move |_request: http::Request<Bytes>| {
+        *counter.lock().unwrap() += 1;
+        async {
+            Err::<http::Response<String>, _>(std::io::Error::from(
+                std::io::ErrorKind::ConnectionRefused,
+            ))
+        }
+    });
+
+    let client = Client::builder("test-api-key", base_url())
+        .http_service(service)
+        .retry_policy(
+            RetryPolicy::default()
+                .max_attempts(2)
+                .base_delay(std::time::Duration::from_millis(1)),
+        )
+        .build()
+        .unwrap();
+    let err = client
+        .responses()
+        .create_text("gpt-test", "hello")
+        .send()
+        .await
+        .expect_err("service error should be returned");
+
+    assert!(
+        matches!(&err, Error::Transport(e) if e.is_retryable()),
+        "expected Transport error, got: {err:?}"
+    );
+    assert_eq!(*calls.lock().unwrap(), 2);
+}
+
+#[tokio::test]
+async fn streams_through_service() {
+    let event = serde_json::json!({
+        "type": "response.output_text.delta",
+        "sequence_number": 0,
+        "item_id": "msg_001",
+        "output_index": 0,
+        "content_index": 0,
+        "delta": "Hi",
+        "logprobs": []
+    });
+    let sse = format!("event: response.output_text.delta\ndata: {event}\n\ndata: [DONE]\n\n");
+    let service = service_fn(move |_request: http::Request<Bytes>| {
+        let sse = sse.clone();
+        async move {
+            Ok::<_, Infallible>(
+                http::Response::builder()
+                    .header("content-type", "text/event-stream")
+                    .body(sse)
+                    .unwrap(),
+            )
+        }
+    });
+
+    let client = Client::builder("test-api-key", base_url())
+        .http_service(service)
+        .build()
+        .unwrap();
+    let mut stream = client
+        .responses()
+        .create_text("gpt-test", "hello")
+        .send_stream()
+        .await
+        .expect("send_stream should succeed");
+
+    let event = std::future::poll_fn(|cx| Pin::new(&mut stream).poll_next(cx))
+        .await
+        .expect("expected an event")
+        .expect("event should decode");
+    assert!(matches!(
+        event,
+        StreamingEvent::ResponseOutputTextDelta { delta, .. } if delta == "Hi"
+    ));
+}
+
+#[tokio::test]
+async fn responses_service_creates_responses() {
+    let server = common::mock_server().await;
+
+    Mock::given(method("POST"))
+        .and(path("/responses"))
+        .and(body_partial_json(serde_json::json!({
+            "model": "gpt-test",
+            "input": "hello"
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(common::success_response_body()))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::test_client(&server);
+    let body: models::CreateResponseBody = serde_json::from_value(serde_json::json!({
+        "model": "gpt-test",
+        "input": "hello",
+        "stream": true
+    }))
+    .unwrap();
+
+    let resp = client
+        .responses()
+        .service()
+        .oneshot(body)
+        .await
+        .expect("request should succeed");
+    assert_eq!(resp.id, "resp_test_123");
+}
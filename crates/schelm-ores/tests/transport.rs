@@ -0,0 +1,234 @@
+pub mod common;
+
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use bytes::Bytes;
+use futures_core::Stream;
+
+use schelm_ores::client::{
+    Body, BoxFuture, Client, Error, InMemoryTransport, Result, RetryPolicy, Transport,
+    TransportError,
+};
+use schelm_ores::models::StreamingEvent;
+
+fn base_url() -> url::Url {
+    url::Url::parse("http://memory.invalid/v1").unwrap()
+}
+
+fn client_with(transport: impl Transport + 'static) -> Client {
+    Client::builder("test-api-key", base_url())
+        .transport(transport)
+        .build()
+        .unwrap()
+}
+
+/// A body stream that yields pre-defined chunks or errors.
+struct Chunks(VecDeque<Result<Bytes>>);
+
+impl Stream for Chunks {
+    type Item = Result<Bytes>;
+
+    fn poll_next(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Poll::Ready(self.0.pop_front())
+    }
+}
+
+fn delta_frame(seq: i32, delta: &str) -> String {
+    let event = serde_json::json!({
+        "type": "response.output_text.delta",
+        "sequence_number": seq,
+        "item_id": "msg_001",
+        "output_index": 0,
+        "content_index": 0,
+        "delta": delta,
+        "logprobs": []
+    });
+    format!("event: response.output_text.delta\ndata: {event}\n\n")
+}
+
+async fn next(
+    stream: &mut schelm_ores::client::ResponseEventStream,
+) -> Option<Result<StreamingEvent>> {
+    std::future::poll_fn(|cx| Pin::new(&mut *stream).poll_next(cx)).await
+}
+
+#[tokio::test]
+async fn in_memory_transport_answers_and_records_requests() {
+    let transport = Arc::new(InMemoryTransport::json(
+        http::StatusCode::OK,
+        common::success_response_body(),
+    ));
+    let client = client_with(transport.clone());
+
+    let resp = client
+        .responses()
+        .create_text("gpt-test", "hello")
+        .send()
+        .await
+        .expect("request should succeed");
+    assert_eq!(resp.id, "resp_test_123");
+
+    let requests = transport.take_requests();
+    assert_eq!(requests.len(), 1);
+    let request = &requests[0];
+    assert_eq!(request.method(), http::Method::POST);
+    assert_eq!(request.uri(), "http://memory.invalid/v1/responses");
+    assert_eq!(request.headers()["authorization"], "Bearer test-api-key");
+    let body: serde_json::Value = serde_json::from_slice(request.body()).unwrap();
+    assert_eq!(body["input"], "hello");
+    assert!(transport.take_requests().is_empty());
+}
+
+#[tokio::test]
+async fn in_memory_transport_streams_chunked_events() {
+    let frame = delta_frame(0, "Hello");
+    let (head, tail) = frame.split_at(frame.len() / 2);
+    let chunks: Vec<String> = vec![
+        head.to_owned(),
+        tail.to_owned(),
+        delta_frame(1, " world"),
+        "data: [DONE]\n\n".to_owned(),
+    ];
+    let transport = InMemoryTransport::new(move |_| {
+        let chunks = chunks.iter().map(|c| Ok(Bytes::from(c.clone()))).collect();
+        Ok(http::Response::builder()
+            .header("content-type", "text/event-stream")
+            .body(Body::from_stream(Chunks(chunks)))
+            .unwrap())
+    });
+    let client = client_with(transport);
+
+    let mut stream = client
+        .responses()
+        .create_text("gpt-test", "hello")
+        .send_stream()
+        .await
+        .expect("send_stream should succeed");
+
+    let mut deltas = Vec::new();
+    while let Some(event) = next(&mut stream).await {
+        if let StreamingEvent::ResponseOutputTextDelta { delta, .. } = event.unwrap() {
+            deltas.push(delta);
+        }
+    }
+    assert_eq!(deltas, ["Hello", " world"]);
+}
+
+#[tokio::test]
+async fn body_errors_end_the_event_stream() {
+    let transport = InMemoryTransport::new(|_| {
+        let chunks = VecDeque::from([
+            Ok(Bytes::from(delta_frame(0, "Hi"))),
+            Err(TransportError::retryable("connection reset").into()),
+        ]);
+        Ok(http::Response::builder()
+            .header("content-type", "text/event-stream")
+            .body(Body::from_stream(Chunks(chunks)))
+            .unwrap())
+    });
+    let client = client_with(transport);
+
+    let mut stream = client
+        .responses()
+        .create_text("gpt-test", "hello")
+        .send_stream()
+        .await
+        .expect("send_stream should succeed");
+
+    assert!(next(&mut stream).await.unwrap().is_ok());
+    let err = next(&mut stream).await.unwrap().unwrap_err();
+    assert!(
+        matches!(err, Error::Transport(_)),
+        "expected Transport error, got: {err:?}"
+    );
+    assert!(next(&mut stream).await.is_none());
+}
+
+#[tokio::test]
+async fn retries_apply_to_custom_transports() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = calls.clone();
+    let transport = InMemoryTransport::new(move |_| {
+        let status = match counter.fetch_add(1, Ordering::SeqCst) {
+            0 => http::StatusCode::SERVICE_UNAVAILABLE,
+            _ => http::StatusCode::OK,
+        };
+        Ok(http::Response::builder()
+            .status(status)
+            .body(Body::from(common::success_response_body().to_string()))
+            .unwrap())
+    });
+    let client = Client::builder("test-api-key", base_url())
+        .transport(transport)
+        .retry_policy(RetryPolicy::default().base_delay(Duration::from_millis(1)))
+        .build()
+        .unwrap();
+
+    client
+        .responses()
+        .create_text("gpt-test", "hello")
+        .send()
+        .await
+        .expect("request should succeed after a retry");
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
+
+/// A transport that always fails with the error made by its function.
+struct Unreachable(AtomicUsize, fn() -> TransportError);
+
+impl Transport for Unreachable {
+    fn send(&self, _request: http::Request<Bytes>) -> BoxFuture<'_, Result<http::Response<Body>>> {
+        self.0.fetch_add(1, Ordering::SeqCst);
+        let err = (self.1)();
+        Box::pin(async { Err(err.into()) })
+    }
+}
+
+async fn list_models_through(transport: &Arc<Unreachable>) -> Error {
+    let policy = RetryPolicy::default()
+        .max_attempts(3)
+        .base_delay(Duration::from_millis(1));
+    let client = Client::builder("test-api-key", base_url())
+        .transport(transport.clone())
+        .retry_policy(policy)
+        .build()
+        .unwrap();
+    client
+        .models()
+        .list()
+        .await
+        .expect_err("transport error should be returned")
+}
+
+#[tokio::test]
+async fn permanent_transport_errors_are_not_retried() {
+    let transport = Arc::new(Unreachable(AtomicUsize::new(0), || {
+        TransportError::new("invalid request")
+    }));
+
+    let err = list_models_through(&transport).await;
+    assert!(
+        matches!(&err, Error::Transport(e) if e.to_string() == "invalid request"),
+        "expected Transport error, got: {err:?}"
+    );
+    assert_eq!(transport.0.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn retryable_transport_errors_are_retried() {
+    let transport = Arc::new(Unreachable(AtomicUsize::new(0), || {
+        TransportError::retryable("no route to host")
+    }));
+
+    let err = list_models_through(&transport).await;
+    assert!(
+        matches!(&err, Error::Transport(e) if e.is_retryable()),
+        "expected Transport error, got: {err:?}"
+    );
+    assert_eq!(transport.0.load(Ordering::SeqCst), 3);
+}